@@ -121,11 +121,29 @@ fn bench_payment_request(c: &mut Criterion) {
     });
 }
 
+fn bench_to_json_value(c: &mut Criterion) {
+    let data = format!("{:?}", generate_data());
+    let parsed = root::<(&str, ErrorKind)>(&data).unwrap().1;
+
+    c.bench_function("bench_to_json_value", |b| {
+        b.iter(|| {
+            let _value = black_box(black_box(&parsed).to_json_value());
+        })
+    });
+    c.bench_function("bench_json_round_trip", |b| {
+        b.iter(|| {
+            let json = serde_json::to_string(black_box(&parsed)).unwrap();
+            let _value = black_box(serde_json::from_str::<serde_json::Value>(&json).unwrap());
+        })
+    });
+}
+
 criterion_group!(
     benches,
     bench_everything,
     bench_everything_dbg_pnt,
     bench_parse_and_serialize,
-    bench_payment_request
+    bench_payment_request,
+    bench_to_json_value
 );
 criterion_main!(benches);
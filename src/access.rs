@@ -0,0 +1,393 @@
+use std::ops::Index;
+
+use indexmap::{map::Entry, IndexMap};
+
+use crate::{traverse::resolve, DataModel};
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Get the [`Entry`] for `key` in a [`DataModel::Map`], mirroring [`IndexMap::entry`]. A
+    /// [`DataModel::Null`] is turned into an empty map first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is neither a map nor null.
+    ///
+    pub fn entry(&mut self, key: &'a str) -> Entry<'_, &'a str, DataModel<'a>> {
+        if let DataModel::Null = self {
+            *self = DataModel::Map(IndexMap::new());
+        }
+
+        match self {
+            DataModel::Map(map) => map.entry(key),
+            _ => panic!("cannot take an entry of a non map value: {:?}", self),
+        }
+    }
+
+    /// Entries of a [`DataModel::Map`], nothing for any other variant.
+    pub fn map_or_empty(&self) -> impl Iterator<Item = (&'a str, &DataModel<'a>)> {
+        let map = match self {
+            DataModel::Map(map) => Some(map),
+            _ => None,
+        };
+
+        map.into_iter().flatten().map(|(key, value)| (*key, value))
+    }
+
+    /// Elements of a [`DataModel::Vec`], an empty slice for any other variant.
+    pub fn vec_or_empty(&self) -> &[DataModel<'a>] {
+        match self {
+            DataModel::Vec(vec) => vec,
+            _ => &[],
+        }
+    }
+
+    ///
+    /// The value at the first of the dotted `paths` that resolves to something other than null,
+    /// e.g. `["customer_id", "customer.id"]` to fall back on a nested field. Array indices are
+    /// path segments too, as in `attempts.0.status`.
+    ///
+    pub fn coalesce(&self, paths: &[&str]) -> Option<&DataModel<'a>> {
+        paths
+            .iter()
+            .filter_map(|path| resolve(self, path))
+            .find(|value| !matches!(value, DataModel::Null))
+    }
+
+    ///
+    /// Keys of the map at the dotted `path`, in input order, or nothing when `path` does not
+    /// resolve to a map. The empty path is the value itself.
+    ///
+    pub fn keys_at(&self, path: &str) -> Option<Vec<&'a str>> {
+        resolve(self, path)?
+            .as_map()
+            .map(|map| map.keys().copied().collect())
+    }
+
+    ///
+    /// Group the maps of a [`DataModel::Vec`] by the scalar value of their `key` field, such as
+    /// the `status` of `attempts`. Groups are ordered by first appearance, and elements that are
+    /// not maps, lack `key` or hold a map or vec under it are left out.
+    ///
+    pub fn group_by(&self, key: &str) -> IndexMap<String, Vec<&DataModel<'a>>> {
+        let mut groups: IndexMap<String, Vec<_>> = IndexMap::new();
+
+        for element in self.vec_or_empty() {
+            let group = match element {
+                DataModel::Map(map) => match map.get(key) {
+                    Some(DataModel::Null) => "null".to_string(),
+                    Some(DataModel::Boolean(data)) => data.to_string(),
+                    Some(DataModel::Integer(data)) => data.to_string(),
+                    Some(DataModel::Float(data)) => data.to_string(),
+                    Some(DataModel::String(data)) => data.to_string(),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            groups.entry(group).or_default().push(element);
+        }
+
+        groups
+    }
+
+    /// Content of a [`DataModel::String`], an empty string for any other variant.
+    pub fn str_or_empty(&self) -> &str {
+        match self {
+            DataModel::String(data) => data,
+            _ => "",
+        }
+    }
+
+    /// Content of a [`DataModel::String`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DataModel::String(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Value of a [`DataModel::Float`], or of a [`DataModel::Integer`] converted to a float.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DataModel::Float(data) => Some(*data),
+            DataModel::Integer(data) => Some(*data as f64),
+            _ => None,
+        }
+    }
+
+    /// Value of a [`DataModel::Boolean`].
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            DataModel::Boolean(data) => Some(*data),
+            _ => None,
+        }
+    }
+
+    /// Entries of a [`DataModel::Map`].
+    pub fn as_map(&self) -> Option<&IndexMap<&'a str, DataModel<'a>>> {
+        match self {
+            DataModel::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Elements of a [`DataModel::Vec`].
+    pub fn as_vec(&self) -> Option<&[DataModel<'a>]> {
+        match self {
+            DataModel::Vec(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is [`DataModel::Null`].
+    pub fn is_null(&self) -> bool {
+        matches!(self, DataModel::Null)
+    }
+
+    /// Value under `key` in a [`DataModel::Map`], nothing for any other variant.
+    pub fn get(&self, key: &str) -> Option<&DataModel<'a>> {
+        match self {
+            DataModel::Map(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Element at `index` in a [`DataModel::Vec`], nothing for any other variant.
+    pub fn get_index(&self, index: usize) -> Option<&DataModel<'a>> {
+        self.vec_or_empty().get(index)
+    }
+}
+
+///
+/// Index into a [`DataModel::Map`] for terse navigation such as `parse["billing"]["address"]`,
+/// see [`DataModel::get`] for the non-panicking form.
+///
+/// # Panics
+///
+/// Panics if the value is not a map or has no entry for `key`.
+///
+impl<'a> Index<&str> for DataModel<'a> {
+    type Output = DataModel<'a>;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("no entry for key {:?} in {:?}", key, self),
+        }
+    }
+}
+
+///
+/// Consume a container: a [`DataModel::Vec`] yields its elements and a [`DataModel::Map`] yields
+/// each entry as a `[key, value]` pair in a two element vec, while scalars yield nothing.
+///
+impl<'a> IntoIterator for DataModel<'a> {
+    type Item = DataModel<'a>;
+    type IntoIter = std::vec::IntoIter<DataModel<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            DataModel::Vec(vec) => vec.into_iter(),
+            DataModel::Map(map) => map
+                .into_iter()
+                .map(|(key, value)| DataModel::Vec(vec![DataModel::String(key.into()), value]))
+                .collect::<Vec<_>>()
+                .into_iter(),
+            _ => Vec::new().into_iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use indexmap::IndexMap;
+    use nom::error::ErrorKind;
+
+    use crate::{root, DataModel};
+
+    #[test]
+    fn test_entry() {
+        let mut model = DataModel::Null;
+
+        model.entry("a").or_insert(DataModel::Null);
+        assert_eq!(model, DataModel::Map([("a", DataModel::Null)].into()));
+
+        *model.entry("a").or_insert(DataModel::Null) = DataModel::Integer(1);
+        model.entry("b").or_insert_with(|| DataModel::Boolean(true));
+        assert_eq!(
+            model,
+            DataModel::Map(IndexMap::from([
+                ("a", DataModel::Integer(1)),
+                ("b", DataModel::Boolean(true)),
+            ]))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_entry_not_map() {
+        let mut model = DataModel::Boolean(false);
+        model.entry("a").or_insert(DataModel::Null);
+    }
+
+    #[test]
+    fn test_or_empty() {
+        let model = DataModel::Boolean(true);
+        assert_eq!(model.map_or_empty().count(), 0);
+        assert!(model.vec_or_empty().is_empty());
+        assert_eq!(model.str_or_empty(), "");
+
+        let model = DataModel::Map([("a", DataModel::String("b".into()))].into());
+        assert_eq!(
+            model.map_or_empty().collect::<Vec<_>>(),
+            vec![("a", &DataModel::String("b".into()))]
+        );
+        assert!(model.vec_or_empty().is_empty());
+
+        let model = DataModel::Vec(vec![DataModel::Null]);
+        assert_eq!(model.vec_or_empty(), &[DataModel::Null]);
+        assert_eq!(DataModel::String("x".into()).str_or_empty(), "x");
+    }
+
+    #[test]
+    fn test_keys_at() {
+        let data = r#"Payment { id: "pay_1", shipping: Some(Address { address: Some(AddressDetails { city: Some("Banglore"), country: Some(IN), zip: None }), phone: None }), attempts: [Attempt { status: Charged }] }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse.keys_at("shipping.address"),
+            Some(vec!["city", "country", "zip"])
+        );
+        assert_eq!(parse.keys_at(""), Some(vec!["id", "shipping", "attempts"]));
+        assert_eq!(parse.keys_at("attempts.0"), Some(vec!["status"]));
+        assert_eq!(parse.keys_at("attempts"), None);
+        assert_eq!(parse.keys_at("shipping.address.city"), None);
+        assert_eq!(parse.keys_at("billing"), None);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let data = r#"[Attempt { id: 1, status: Failed }, Attempt { id: 2, status: Charged }, Attempt { id: 3, status: Failed }, Attempt { id: 4 }, 5]"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        let attempts = parse.vec_or_empty();
+
+        let groups = parse.group_by("status");
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["Failed", "Charged"]);
+        assert_eq!(groups["Failed"], vec![&attempts[0], &attempts[2]]);
+        assert_eq!(groups["Charged"], vec![&attempts[1]]);
+
+        let groups = parse.group_by("id");
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups["4"], vec![&attempts[3]]);
+        assert!(DataModel::Null.group_by("status").is_empty());
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let data = r#"Payment { customer_id: None, customer: Some(Customer { id: "cus_1" }), attempts: [Attempt { status: Charged }] }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse.coalesce(&["customer_id", "customer.id"]),
+            Some(&DataModel::String("cus_1".into()))
+        );
+        assert_eq!(
+            parse.coalesce(&["customer.name", "attempts.0.status", "customer.id"]),
+            Some(&DataModel::String("Charged".into()))
+        );
+        assert_eq!(
+            parse.coalesce(&["customer_id", "attempts.1", "attempts.x"]),
+            None
+        );
+        assert_eq!(parse.coalesce(&[]), None);
+    }
+
+    #[test]
+    fn test_as() {
+        let data = r#"Payment { id: "pay_1", amount: 65, ratio: 0.5, paid: true, tags: ["a"], customer: None }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(parse["id"].as_str(), Some("pay_1"));
+        assert_eq!(parse["amount"].as_str(), None);
+        assert_eq!(parse["amount"].as_f64(), Some(65.0));
+        assert_eq!(parse["ratio"].as_f64(), Some(0.5));
+        assert_eq!(parse["id"].as_f64(), None);
+        assert_eq!(parse["paid"].as_bool(), Some(true));
+        assert_eq!(parse["customer"].as_bool(), None);
+        assert_eq!(parse.as_map().map(IndexMap::len), Some(6));
+        assert_eq!(parse["tags"].as_map(), None);
+        assert_eq!(
+            parse["tags"].as_vec(),
+            Some(&[DataModel::String("a".into())][..])
+        );
+        assert_eq!(parse.as_vec(), None);
+        assert!(parse["customer"].is_null());
+        assert!(!parse["paid"].is_null());
+    }
+
+    #[test]
+    fn test_get() {
+        let data = r#"PaymentsResponse { payment_id: Some("VND9P0YMT7S91EZF7NK2"), status: Succeeded, amount: 370, billing: Some(Address { address: Some(AddressDetails { city: Some("Bengaluru"), country: Some(DE), line2: None }), phone: Some(PhoneDetails { number: None }) }), allowed_payment_method_types: Some(Array [String("credit"), String("debit")]) }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse["billing"]["address"]["city"],
+            DataModel::String("Bengaluru".into())
+        );
+        assert_eq!(parse["billing"]["address"]["line2"], DataModel::Null);
+        assert_eq!(
+            parse
+                .get("billing")
+                .and_then(|billing| billing.get("phone"))
+                .and_then(|phone| phone.get("number")),
+            Some(&DataModel::Null)
+        );
+        assert_eq!(parse.get("shipping"), None);
+        assert_eq!(parse["amount"].get("value"), None);
+
+        let methods = &parse["allowed_payment_method_types"];
+        assert_eq!(
+            methods.get_index(1),
+            Some(&DataModel::String("debit".into()))
+        );
+        assert_eq!(methods.get_index(2), None);
+        assert_eq!(parse.get_index(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_missing_key() {
+        let parse = root::<(&str, ErrorKind)>("Address { city: None }")
+            .unwrap()
+            .1;
+        let _ = &parse["country"];
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let data = r#"[1, "two", None, Point { x: 3 }]"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        assert_eq!(
+            parse.into_iter().collect::<Vec<DataModel>>(),
+            vec![
+                DataModel::Integer(1),
+                DataModel::String("two".into()),
+                DataModel::Null,
+                DataModel::Map([("x", DataModel::Integer(3))].into()),
+            ]
+        );
+
+        let data = r#"Point { x: 3, y: 4 }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        assert_eq!(
+            parse.into_iter().collect::<Vec<_>>(),
+            vec![
+                DataModel::Vec(vec![DataModel::String("x".into()), DataModel::Integer(3)]),
+                DataModel::Vec(vec![DataModel::String("y".into()), DataModel::Integer(4)]),
+            ]
+        );
+        assert_eq!(DataModel::Integer(1).into_iter().count(), 0);
+    }
+}
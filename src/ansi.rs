@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+const ESCAPE: char = '\x1b';
+
+///
+/// Remove the ANSI escape sequences, such as the colors `\x1b[31m` and `\x1b[0m`, that end up in
+/// debug output copied from a colorized terminal. The parser works on borrowed input, so this is
+/// a separate step to run before [`crate::root`]. The input is borrowed back when it holds no
+/// escape sequence.
+///
+pub fn strip_ansi(input: &str) -> Cow<'_, str> {
+    if !input.contains(ESCAPE) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(item) = chars.next() {
+        if item != ESCAPE {
+            output.push(item);
+            continue;
+        }
+
+        match chars.next() {
+            // control sequence: parameters and intermediates up to a final byte in `@..=~`
+            Some('[') => {
+                for item in chars.by_ref() {
+                    if ('@'..='~').contains(&item) {
+                        break;
+                    }
+                }
+            }
+            // operating system command: terminated by BEL or by the string terminator `ESC \`
+            Some(']') => {
+                while let Some(item) = chars.next() {
+                    if item == '\x07' || (item == ESCAPE && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::borrow::Cow;
+
+    use nom::error::ErrorKind;
+
+    use super::strip_ansi;
+    use crate::{root, DataModel};
+
+    #[test]
+    fn test_strip_ansi() {
+        let data = "\x1b[1mPayment\x1b[0m { \x1b[36mid\x1b[0m: \x1b[32m\"pay_1\"\x1b[0m, \x1b]8;;https://example.com\x07amount\x1b]8;;\x1b\\: \x1b[38;5;208m65\x1b[m }";
+        let stripped = strip_ansi(data);
+        assert_eq!(stripped, r#"Payment { id: "pay_1", amount: 65 }"#);
+
+        let parse = root::<(&str, ErrorKind)>(&stripped).unwrap();
+        assert_eq!(
+            parse.1,
+            DataModel::Map(
+                [
+                    ("id", DataModel::String("pay_1".into())),
+                    ("amount", DataModel::Integer(65))
+                ]
+                .into()
+            ),
+            "residue: {}",
+            parse.0
+        );
+
+        assert!(matches!(strip_ansi("Payment { id: 1 }"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,203 @@
+use serde_json::{json, Value};
+
+use crate::DataModel;
+
+///
+/// [`DiffSummary`] counts the operations of [`DataModel::diff_json`] by kind, for gates such as
+/// "a dump may change in at most N places".
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl DiffSummary {
+    /// Number of operations of any kind.
+    pub fn total(&self) -> usize {
+        self.added + self.removed + self.changed
+    }
+}
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Compute a json patch (RFC 6902) of `add`, `remove` and `replace` operations transforming
+    /// `a` into `b`, with paths given as json pointers.
+    ///
+    pub fn diff_json(a: &DataModel<'a>, b: &DataModel<'a>) -> Value {
+        let mut operations = Vec::new();
+        diff_json_inner(a, b, String::new(), &mut operations);
+        Value::Array(operations)
+    }
+
+    /// Count the `add`, `remove` and `replace` operations of [`DataModel::diff_json`].
+    pub fn diff_summary(a: &DataModel<'a>, b: &DataModel<'a>) -> DiffSummary {
+        let mut summary = DiffSummary::default();
+        let Value::Array(operations) = DataModel::diff_json(a, b) else {
+            return summary;
+        };
+
+        for operation in operations {
+            match operation["op"].as_str() {
+                Some("add") => summary.added += 1,
+                Some("remove") => summary.removed += 1,
+                _ => summary.changed += 1,
+            }
+        }
+
+        summary
+    }
+
+    ///
+    /// Whether `a` and `b` have the same shape: the same variants, the same map keys and the same
+    /// array lengths, all the way down. Scalar contents are not compared.
+    ///
+    pub fn equal_structure(a: &DataModel<'_>, b: &DataModel<'_>) -> bool {
+        match (a, b) {
+            (DataModel::Map(left), DataModel::Map(right)) => {
+                left.len() == right.len()
+                    && left.iter().all(|(key, value)| {
+                        right
+                            .get(key)
+                            .is_some_and(|other| DataModel::equal_structure(value, other))
+                    })
+            }
+            (DataModel::Vec(left), DataModel::Vec(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(right)
+                        .all(|(value, other)| DataModel::equal_structure(value, other))
+            }
+            _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+        }
+    }
+}
+
+fn to_json(value: &DataModel<'_>) -> Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+fn pointer(path: &str, segment: &str) -> String {
+    format!("{}/{}", path, segment.replace('~', "~0").replace('/', "~1"))
+}
+
+fn diff_json_inner(a: &DataModel<'_>, b: &DataModel<'_>, path: String, output: &mut Vec<Value>) {
+    match (a, b) {
+        (DataModel::Map(left), DataModel::Map(right)) => {
+            for key in left.keys().filter(|key| !right.contains_key(*key)) {
+                output.push(json!({ "op": "remove", "path": pointer(&path, key) }));
+            }
+            for (key, value) in left {
+                if let Some(other) = right.get(key) {
+                    diff_json_inner(value, other, pointer(&path, key), output);
+                }
+            }
+            for (key, value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+                output.push(
+                    json!({ "op": "add", "path": pointer(&path, key), "value": to_json(value) }),
+                );
+            }
+        }
+        (DataModel::Vec(left), DataModel::Vec(right)) => {
+            for (index, (value, other)) in left.iter().zip(right).enumerate() {
+                diff_json_inner(value, other, pointer(&path, &index.to_string()), output);
+            }
+            for index in (right.len()..left.len()).rev() {
+                output.push(json!({ "op": "remove", "path": pointer(&path, &index.to_string()) }));
+            }
+            for (index, value) in right.iter().enumerate().skip(left.len()) {
+                output.push(json!({ "op": "add", "path": pointer(&path, &index.to_string()), "value": to_json(value) }));
+            }
+        }
+        _ if a != b => {
+            output.push(json!({ "op": "replace", "path": path, "value": to_json(b) }));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+    use serde_json::{json, Value};
+
+    use crate::*;
+
+    #[test]
+    fn test_diff_json() {
+        let before =
+            r#"Payment { amount: 100, address: Address { city: "Banglore" }, tags: ["a"] }"#;
+        let after = r#"Payment { amount: 200, address: Address { city: "Banglore", zip: "560/001" }, tags: ["a", "b"] }"#;
+        let before = root::<(&str, ErrorKind)>(before).unwrap().1;
+        let after = root::<(&str, ErrorKind)>(after).unwrap().1;
+
+        let Value::Array(operations) = DataModel::diff_json(&before, &after) else {
+            panic!("expected an array of operations")
+        };
+
+        assert_eq!(
+            operations,
+            vec![
+                json!({ "op": "replace", "path": "/amount", "value": 200 }),
+                json!({ "op": "add", "path": "/address/zip", "value": "560/001" }),
+                json!({ "op": "add", "path": "/tags/1", "value": "b" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_summary() {
+        let before = r#"Payment { amount: 100, status: Pending, customer: Some("cus_1"), tags: ["a", "b"], address: Address { city: "Banglore", zip: None } }"#;
+        let after = r#"Payment { amount: 200, status: Charged, tags: ["a"], address: Address { city: "Bengaluru", zip: None, state: "KA" }, retries: 1 }"#;
+        let before = root::<(&str, ErrorKind)>(before).unwrap().1;
+        let after = root::<(&str, ErrorKind)>(after).unwrap().1;
+
+        let summary = DataModel::diff_summary(&before, &after);
+        assert_eq!(
+            summary,
+            DiffSummary {
+                added: 2,
+                removed: 2,
+                changed: 3,
+            }
+        );
+        assert_eq!(summary.total(), 7);
+        assert_eq!(
+            DataModel::diff_summary(&before, &before),
+            DiffSummary::default()
+        );
+    }
+
+    #[test]
+    fn test_diff_json_equal() {
+        let model = DataModel::Vec(vec![DataModel::Integer(1), DataModel::Null]);
+        assert_eq!(DataModel::diff_json(&model, &model), json!([]));
+        assert_eq!(
+            DataModel::diff_json(&model, &DataModel::Null),
+            json!([{ "op": "replace", "path": "", "value": null }])
+        );
+    }
+
+    #[test]
+    fn test_equal_structure() {
+        let a = r#"Payment { amount: 100, address: Address { city: "Banglore" }, tags: ["a", "b"], paid: true }"#;
+        let b = r#"Payment { amount: 250, address: Address { city: "Paris" }, tags: ["x", "y"], paid: false }"#;
+        let a = root::<(&str, ErrorKind)>(a).unwrap().1;
+        let b = root::<(&str, ErrorKind)>(b).unwrap().1;
+        assert!(DataModel::equal_structure(&a, &b));
+
+        for other in [
+            r#"Payment { amount: 100, address: Address { town: "Banglore" }, tags: ["a", "b"], paid: true }"#,
+            r#"Payment { amount: 100, address: Address { city: "Banglore" }, tags: ["a"], paid: true }"#,
+            r#"Payment { amount: "100", address: Address { city: "Banglore" }, tags: ["a", "b"], paid: true }"#,
+            r#"Payment { amount: 100, address: Address { city: "Banglore" }, tags: ["a", "b"] }"#,
+        ] {
+            let other = root::<(&str, ErrorKind)>(other).unwrap().1;
+            assert!(!DataModel::equal_structure(&a, &other), "{:?}", other);
+        }
+    }
+}
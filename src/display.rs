@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::DataModel;
+
+///
+/// Render the tree back in the syntax of the debug output of std collections: maps as
+/// `{"key": value}`, vecs as `[value]`, strings quoted and escaped, floats always with their
+/// fraction and null as `None`. The alternate flag, `{:#}`, indents the output like `{:#?}`
+/// does. The output parses back into an equal model.
+///
+impl fmt::Display for DataModel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataModel::Null => f.write_str("None"),
+            DataModel::Boolean(data) => write!(f, "{}", data),
+            DataModel::Integer(data) => write!(f, "{}", data),
+            DataModel::Float(data) => write!(f, "{:?}", data),
+            DataModel::String(data) => write!(f, "{:?}", data),
+            DataModel::Map(map) => f
+                .debug_map()
+                .entries(map.iter().map(|(key, value)| (key, Rendered(value))))
+                .finish(),
+            DataModel::Vec(vec) => f.debug_list().entries(vec.iter().map(Rendered)).finish(),
+        }
+    }
+}
+
+/// Adapter letting the std debug builders, which take care of the indentation, render children
+/// through [`fmt::Display`].
+struct Rendered<'b, 'a>(&'b DataModel<'a>);
+
+impl fmt::Debug for Rendered<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use indexmap::IndexMap;
+    use nom::error::ErrorKind;
+
+    use crate::{root, DataModel};
+
+    #[test]
+    fn test_display() {
+        let data = r#"Payment { id: "pay_\"1\"", amount: 65.0, count: -2, paid: true, refund: None, tags: [], meta: {}, attempts: [Attempt { status: Charged }] }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        let compact = parse.to_string();
+        assert_eq!(
+            compact,
+            r#"{"id": "pay_\"1\"", "amount": 65.0, "count": -2, "paid": true, "refund": None, "tags": [], "meta": {}, "attempts": [{"status": "Charged"}]}"#
+        );
+        assert_eq!(
+            root::<(&str, ErrorKind)>(&compact).unwrap(),
+            ("", parse.clone())
+        );
+
+        let pretty = format!("{:#}", parse);
+        assert_eq!(root::<(&str, ErrorKind)>(&pretty).unwrap(), ("", parse));
+    }
+
+    #[test]
+    fn test_display_empty() {
+        assert_eq!(DataModel::Vec(vec![]).to_string(), "[]");
+        assert_eq!(format!("{:#}", DataModel::Map(IndexMap::new())), "{}");
+        assert_eq!(DataModel::Float(f64::NAN).to_string(), "NaN");
+    }
+}
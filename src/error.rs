@@ -0,0 +1,185 @@
+use nom::error::{VerboseError, VerboseErrorKind};
+
+///
+/// [`ParseError`] is returned by [`crate::parse`] for input that is not valid debug output. It
+/// points at the byte `offset` where parsing gave up and carries the stack of parsers that were
+/// running at that point, outermost first, e.g. `["struct", "struct map", "map"]`.
+///
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct ParseError {
+    pub offset: usize,
+    pub message: String,
+    pub context: Vec<&'static str>,
+}
+
+impl ParseError {
+    pub(crate) fn trailing(input: &str, rest: &str) -> Self {
+        Self {
+            offset: input.len() - rest.len(),
+            message: "unexpected trailing input".to_string(),
+            context: vec![],
+        }
+    }
+
+    pub(crate) fn from_nom(input: &str, error: nom::Err<VerboseError<&str>>) -> Self {
+        let error = match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => error,
+            nom::Err::Incomplete(_) => {
+                return Self {
+                    offset: input.len(),
+                    message: "unexpected end of input".to_string(),
+                    context: vec![],
+                }
+            }
+        };
+
+        let offset = error
+            .errors
+            .first()
+            .map_or(0, |(rest, _)| input.len() - rest.len());
+        let message = error
+            .errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Char(item) => Some(format!("expected `{}`", item)),
+                VerboseErrorKind::Nom(nom::error::ErrorKind::TooLarge) => {
+                    Some("nested deeper than the maximum depth".to_string())
+                }
+                VerboseErrorKind::Nom(kind) => Some(format!("failed at {}", kind.description())),
+                VerboseErrorKind::Context(_) => None,
+            })
+            .unwrap_or_else(|| "invalid input".to_string());
+        let context = error
+            .errors
+            .iter()
+            .rev()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(*context),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            offset,
+            message,
+            context,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)?;
+        match self.context.is_empty() {
+            true => Ok(()),
+            false => write!(f, " (in {})", self.context.join(" > ")),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::ParseError;
+    use crate::{parse, parse_with, DataModel, ParseOptions, Variants, DEFAULT_MAX_DEPTH};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse(" Foo { a: 1 } "),
+            Ok(DataModel::Map([("a", DataModel::Integer(1))].into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let data = r#"Payment { id: PaymentId("pay_1"), amount: Some(5) }"#;
+        let options = ParseOptions::new()
+            .variants(Variants::Tagged)
+            .keep_some(true);
+        assert_eq!(
+            parse_with(data, &options),
+            Ok(DataModel::Map(
+                [(
+                    "Payment",
+                    DataModel::Map(
+                        [
+                            (
+                                "id",
+                                DataModel::Map(
+                                    [("PaymentId", DataModel::String("pay_1".into()))].into()
+                                )
+                            ),
+                            (
+                                "amount",
+                                DataModel::Map([("Some", DataModel::Integer(5))].into())
+                            ),
+                        ]
+                        .into()
+                    )
+                )]
+                .into()
+            ))
+        );
+        assert_eq!(parse_with(data, &ParseOptions::new()), parse(data));
+
+        let error = parse_with("[1.5]", &ParseOptions::new().strict_integers(true)).unwrap_err();
+        assert_eq!(error.offset, 1);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let nested = |depth: usize| format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+
+        // the default has to fit the stack of a test thread, even unoptimized
+        let error = parse(&nested(100_000)).unwrap_err();
+        assert_eq!(error.offset, DEFAULT_MAX_DEPTH);
+        assert_eq!(error.message, "nested deeper than the maximum depth");
+        assert!(error.context.contains(&"max depth"));
+        assert!(parse(&nested(DEFAULT_MAX_DEPTH - 1)).is_ok());
+
+        let structs = |depth: usize| format!("{}1{}", "A { a: ".repeat(depth), " }".repeat(depth));
+        assert!(parse(&structs(DEFAULT_MAX_DEPTH - 1)).is_ok());
+        assert_eq!(
+            parse(&structs(DEFAULT_MAX_DEPTH)).unwrap_err().message,
+            "nested deeper than the maximum depth"
+        );
+
+        let lenient = ParseOptions::new().lenient(true);
+        let error = parse_with(&nested(100_000), &lenient).unwrap_err();
+        assert_eq!(error.offset, DEFAULT_MAX_DEPTH);
+        assert_eq!(error.message, "nested deeper than the maximum depth");
+        assert!(parse_with("[1, ?]", &lenient).is_ok());
+
+        let options = ParseOptions::new().max_depth(2);
+        assert!(parse_with("[1]", &options).is_ok());
+        assert_eq!(parse_with("[[1]]", &options).unwrap_err().offset, 2);
+        assert!(parse_with(&nested(3), &ParseOptions::new()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let data = "Foo { a: [1; 2] }";
+        let error = parse(data).unwrap_err();
+        assert_eq!(error.offset, data.find(';').unwrap_or_default());
+        assert_eq!(error.message, "expected `]`");
+        assert_eq!(error.context, vec!["struct", "struct map", "array"]);
+        assert_eq!(
+            error.to_string(),
+            "expected `]` at offset 11 (in struct > struct map > array)"
+        );
+
+        assert_eq!(parse("[ \"12\"; 23]").unwrap_err().offset, 6);
+        assert_eq!(
+            parse("Foo { a: 1 } }"),
+            Err(ParseError {
+                offset: 13,
+                message: "unexpected trailing input".to_string(),
+                context: vec![],
+            })
+        );
+    }
+}
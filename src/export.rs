@@ -0,0 +1,198 @@
+use indexmap::IndexMap;
+use serde_json::{Number, Value};
+
+use crate::DataModel;
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Flatten the tree into environment variables: path segments are uppercased and joined with
+    /// `_` after `prefix`, array elements use their index as the segment and nulls are skipped,
+    /// e.g. `PREFIX_SHIPPING_ADDRESS_CITY=Banglore`.
+    ///
+    pub fn to_env_vars(&self, prefix: &str) -> IndexMap<String, String> {
+        fn inner(node: &DataModel<'_>, name: String, output: &mut IndexMap<String, String>) {
+            let join = |segment: &str| match name.is_empty() {
+                true => segment.to_uppercase(),
+                false => format!("{}_{}", name, segment.to_uppercase()),
+            };
+
+            match node {
+                DataModel::Null => {}
+                DataModel::Boolean(data) => {
+                    output.insert(name, data.to_string());
+                }
+                DataModel::Integer(data) => {
+                    output.insert(name, data.to_string());
+                }
+                DataModel::Float(data) => {
+                    output.insert(name, data.to_string());
+                }
+                DataModel::String(data) => {
+                    output.insert(name, data.to_string());
+                }
+                DataModel::Map(map) => map
+                    .iter()
+                    .for_each(|(key, value)| inner(value, join(key), output)),
+                DataModel::Vec(vec) => vec
+                    .iter()
+                    .enumerate()
+                    .for_each(|(index, value)| inner(value, join(&index.to_string()), output)),
+            }
+        }
+
+        let mut output = IndexMap::new();
+        inner(self, prefix.to_uppercase(), &mut output);
+        output
+    }
+
+    ///
+    /// Serialize the elements of a [`DataModel::Vec`] as json lines, one compact json document
+    /// per element each ended by a newline. Nothing for any other variant.
+    ///
+    pub fn to_json_lines(&self) -> Option<String> {
+        let mut output = String::new();
+        for element in self.as_vec()? {
+            output.push_str(&serde_json::to_string(element).unwrap_or_default());
+            output.push('\n');
+        }
+        Some(output)
+    }
+
+    ///
+    /// Deserialize the tree into a typed value, going through a [`serde_json::Value`], so that a
+    /// debug dump can be turned back into the struct that printed it.
+    ///
+    pub fn try_into_typed<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_value(self.to_json_value())
+    }
+
+    ///
+    /// Build a [`serde_json::Value`] straight from the borrowed tree, copying every string once
+    /// instead of cloning the tree or serializing it to a string and parsing that back. Gives the
+    /// same value as the [`From`] conversion.
+    ///
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            DataModel::Null => Value::Null,
+            DataModel::Boolean(data) => Value::Bool(*data),
+            DataModel::Integer(data) => Value::Number((*data).into()),
+            DataModel::Float(data) => Number::from_f64(*data)
+                .map_or_else(|| Value::String(format!("{:?}", data)), Value::Number),
+            DataModel::String(data) => Value::String(data.to_string()),
+            DataModel::Map(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.to_string(), value.to_json_value()))
+                    .collect(),
+            ),
+            DataModel::Vec(vec) => Value::Array(vec.iter().map(Self::to_json_value).collect()),
+        }
+    }
+}
+
+///
+/// Convert into a [`serde_json::Value`] without going through a string. Non finite floats become
+/// the strings `"NaN"`, `"inf"` and `"-inf"`, as they do when serializing.
+///
+impl<'a> From<DataModel<'a>> for Value {
+    fn from(value: DataModel<'a>) -> Self {
+        match value {
+            DataModel::Null => Value::Null,
+            DataModel::Boolean(data) => Value::Bool(data),
+            DataModel::Integer(data) => Value::Number(data.into()),
+            DataModel::Float(data) => Number::from_f64(data)
+                .map_or_else(|| Value::String(format!("{:?}", data)), Value::Number),
+            DataModel::String(data) => Value::String(data.into_owned()),
+            DataModel::Map(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| (key.to_string(), value.into()))
+                    .collect(),
+            ),
+            DataModel::Vec(vec) => Value::Array(vec.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+    use serde_json::{json, Value};
+
+    use crate::*;
+
+    #[test]
+    fn test_to_env_vars() {
+        let data = r#"Config { a: Inner { b: 1, c: None }, hosts: ["x", "y"], debug: true }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse.to_env_vars("prefix").into_iter().collect::<Vec<_>>(),
+            vec![
+                ("PREFIX_A_B".to_string(), "1".to_string()),
+                ("PREFIX_HOSTS_0".to_string(), "x".to_string()),
+                ("PREFIX_HOSTS_1".to_string(), "y".to_string()),
+                ("PREFIX_DEBUG".to_string(), "true".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_into_value() {
+        let data =
+            r#"Reading { value: NaN, low: -inf, ratio: 0.5, ok: true, tags: ["a"], unit: None }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            Value::from(parse),
+            json!({ "value": "NaN", "low": "-inf", "ratio": 0.5, "ok": true, "tags": ["a"], "unit": null })
+        );
+    }
+
+    #[test]
+    fn test_to_json_value() {
+        let data = r#"Reading { value: NaN, low: -inf, ratio: 0.5, whole: 2.0, ok: true, tags: ["a", *** alloc::string::String ***], unit: None, inner: Inner { id: -1, list: [] } }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        let round_trip: Value =
+            serde_json::from_str(&serde_json::to_string(&parse).unwrap()).unwrap();
+        assert_eq!(parse.to_json_value(), round_trip);
+        assert_eq!(parse.to_json_value(), Value::from(parse));
+    }
+
+    #[test]
+    fn test_to_json_lines() {
+        let data = r#"[Attempt { id: 1, status: Charged }, Attempt { id: 2, status: Failed }]"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse.to_json_lines().unwrap(),
+            "{\"id\":1,\"status\":\"Charged\"}\n{\"id\":2,\"status\":\"Failed\"}\n"
+        );
+        assert_eq!(DataModel::Vec(vec![]).to_json_lines(), Some(String::new()));
+        assert_eq!(parse.vec_or_empty()[0].to_json_lines(), None);
+    }
+
+    #[test]
+    fn test_try_into_typed() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Bob {
+            inner_int: f64,
+            inner_string: String,
+        }
+
+        let data = r#"Bob { inner_int: 123.0, inner_string: "data" }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        assert_eq!(
+            parse.try_into_typed::<Bob>().unwrap(),
+            Bob {
+                inner_int: 123.0,
+                inner_string: "data".to_string(),
+            }
+        );
+
+        let data = r#"Bob { inner_int: "123", inner_string: "data" }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        assert!(parse.try_into_typed::<Bob>().is_err());
+    }
+}
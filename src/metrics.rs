@@ -0,0 +1,240 @@
+use std::{
+    borrow::Cow,
+    hash::{Hash, Hasher},
+};
+
+use crate::DataModel;
+
+///
+/// 64 bit FNV-1a. `DefaultHasher` leaves its algorithm unspecified and free to change between
+/// releases, which would break hashes persisted for [`DataModel::content_hash`]. Integers are fed
+/// little endian and `usize` as a `u64` so the hash is also the same on 32 bit targets like wasm.
+///
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+}
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Whether this node is a masked value, i.e. a string of the form `*** ... ***`
+    ///
+    pub fn is_masked(&self) -> bool {
+        matches!(self, DataModel::String(data) if data.len() > 7 && data.starts_with("*** ") && data.ends_with(" ***"))
+    }
+
+    /// Number of masked values in the tree.
+    pub fn count_masked(&self) -> usize {
+        self.fold(0, |count, node| count + usize::from(node.is_masked()))
+    }
+
+    /// Number of [`DataModel::Null`] values in the tree.
+    pub fn count_nulls(&self) -> usize {
+        self.fold(0, |count, node| {
+            count + usize::from(matches!(node, DataModel::Null))
+        })
+    }
+
+    /// Number of keys across every map in the tree.
+    pub fn count_keys(&self) -> usize {
+        self.fold(0, |count, node| match node {
+            DataModel::Map(map) => count + map.len(),
+            _ => count,
+        })
+    }
+
+    /// Number of scalar (non map, non vec) values in the tree.
+    pub fn leaf_count(&self) -> usize {
+        self.fold(0, |count, node| match node {
+            DataModel::Map(_) | DataModel::Vec(_) => count,
+            _ => count + 1,
+        })
+    }
+
+    /// Length of the longest vec anywhere in the tree.
+    pub fn max_array_len(&self) -> usize {
+        self.fold(0, |longest, node| match node {
+            DataModel::Vec(vec) => longest.max(vec.len()),
+            _ => longest,
+        })
+    }
+
+    ///
+    /// Hash of the content of the tree, independent of the order of map entries. The hash uses a
+    /// fixed algorithm, so it is stable across runs, builds and platforms, which makes it suitable
+    /// for deduplication and change detection of persisted dumps.
+    ///
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    ///
+    /// Rough estimate, in bytes, of the memory held by the tree: the node itself, the heap of
+    /// owned strings and the capacity of every map and vec. Strings borrowed from the input are
+    /// not counted since they live in the input.
+    ///
+    pub fn byte_size_estimate(&self) -> usize {
+        let node = std::mem::size_of::<DataModel<'_>>();
+        // an index map keeps its entries, their hash and an index table
+        let entry = std::mem::size_of::<(&str, DataModel<'_>)>()
+            + std::mem::size_of::<u64>()
+            + std::mem::size_of::<usize>();
+
+        self.fold(node, |size, element| match element {
+            DataModel::String(Cow::Owned(data)) => size + data.capacity(),
+            DataModel::Map(map) => size + map.capacity() * entry,
+            DataModel::Vec(vec) => size + vec.capacity() * node,
+            _ => size,
+        })
+    }
+
+    ///
+    /// Maximum nesting depth of the tree, scalars have a depth of 0 and every map or vec adds one
+    /// to the depth of its deepest child.
+    ///
+    pub fn depth(&self) -> usize {
+        match self {
+            DataModel::Map(map) => 1 + map.values().map(DataModel::depth).max().unwrap_or(0),
+            DataModel::Vec(vec) => 1 + vec.iter().map(DataModel::depth).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::hash::Hasher;
+
+    use nom::error::ErrorKind;
+
+    use super::Fnv1a;
+    use crate::*;
+
+    #[test]
+    fn test_counts() {
+        let data = r#"PaymentsRequest { payment_id: None, amount: Some(Value(6500)), shipping: Some(Address { address: Some(AddressDetails { city: Some("Banglore"), line1: Some(*** alloc::string::String ***), zip: Some(*** alloc::string::String ***), last_name: None }), phone: Some(PhoneDetails { number: Some(*** alloc::string::String ***), country_code: Some("+1") }) }) }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(parse.count_masked(), 3);
+        assert_eq!(parse.count_nulls(), 2);
+        assert_eq!(parse.count_keys(), 11);
+    }
+
+    #[test]
+    fn test_depth() {
+        let data = "Outer { flat: 1, inner: Inner { list: [1, 2], empty: [] } }";
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(parse.depth(), 3);
+        assert_eq!(DataModel::Null.depth(), 0);
+        assert_eq!(DataModel::Vec(vec![]).depth(), 1);
+    }
+
+    #[test]
+    fn test_shape_metrics() {
+        let data =
+            "Data { short: [1, 2], long: [Inner { list: [1, 2, 3, 4, 5, 6, 7] }], name: None }";
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(parse.leaf_count(), 10);
+        assert_eq!(parse.max_array_len(), 7);
+        assert_eq!(DataModel::Boolean(true).leaf_count(), 1);
+        assert_eq!(DataModel::Boolean(true).max_array_len(), 0);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let left = root::<(&str, ErrorKind)>(r#"Foo { a: 1, b: [true, "x"], c: Bar { d: -0.0 } }"#)
+            .unwrap()
+            .1;
+        let right = root::<(&str, ErrorKind)>(r#"Foo { c: Bar { d: 0.0 }, b: [true, "x"], a: 1 }"#)
+            .unwrap()
+            .1;
+        let other = root::<(&str, ErrorKind)>(r#"Foo { a: 1, b: [true, "y"], c: Bar { d: 0.0 } }"#)
+            .unwrap()
+            .1;
+
+        assert_eq!(left.content_hash(), right.content_hash());
+        assert_ne!(left.content_hash(), other.content_hash());
+        assert_ne!(
+            DataModel::Vec(vec![]).content_hash(),
+            DataModel::Map([].into()).content_hash()
+        );
+    }
+
+    #[test]
+    fn test_content_hash_golden() {
+        let data = root::<(&str, ErrorKind)>(r#"Foo { a: 1, b: [true, "x", None], c: 1.5 }"#)
+            .unwrap()
+            .1;
+
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(data.content_hash(), 0xdda5_5b08_4eda_c5c9);
+    }
+
+    #[test]
+    fn test_byte_size_estimate() {
+        let sizes = [
+            "1",
+            "[1]",
+            "[1, [2]]",
+            r#"[1, [2], "with \"escape\""]"#,
+            r#"[1, [2], "with \"escape\"", Inner { list: [1, 2, 3] }]"#,
+            r#"[1, [2], "with \"escape\"", Inner { list: [1, 2, 3], name: "x" }]"#,
+        ]
+        .map(|data| {
+            root::<(&str, ErrorKind)>(data)
+                .unwrap()
+                .1
+                .byte_size_estimate()
+        });
+
+        assert!(
+            sizes.windows(2).all(|pair| pair[0] < pair[1]),
+            "{:?}",
+            sizes
+        );
+        assert_eq!(sizes[0], std::mem::size_of::<DataModel>());
+    }
+}
@@ -0,0 +1,239 @@
+use std::cell::{Cell, RefCell};
+
+///
+/// [`ParseOptions`] tunes how the parser treats input that is ambiguous or outside of what the
+/// strict grammar accepts. The default matches the behaviour of [`crate::root`]. Options are
+/// given to [`crate::parse_with`] or [`crate::root_with`], which make them visible to every
+/// combinator for the duration of the parse.
+///
+/// - [`ParseOptions::lenient`]: capture unparsable values verbatim, off by default.
+/// - [`ParseOptions::variants`]: unwrap or tag tuple variants, [`Variants::Transparent`].
+/// - [`ParseOptions::keep_some`]: tag `Some(value)`, off by default.
+/// - [`ParseOptions::masks`]: redact, preserve or replace masked values, [`Masks::Redact`].
+/// - [`ParseOptions::units`]: what unit identifiers become, [`Units::String`].
+/// - [`ParseOptions::strict_integers`]: reject fractional numbers, off by default.
+/// - [`ParseOptions::serde_json`]: read `serde_json::Value` debug output, off by default.
+/// - [`ParseOptions::max_depth`]: deepest nesting accepted, [`DEFAULT_MAX_DEPTH`].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseOptions {
+    pub(crate) lenient: bool,
+    pub(crate) variants: Variants,
+    pub(crate) keep_some: bool,
+    pub(crate) masks: Masks,
+    pub(crate) units: Units,
+    pub(crate) strict_integers: bool,
+    pub(crate) serde_json: bool,
+    pub(crate) max_depth: usize,
+}
+
+///
+/// Nesting depth accepted by default, see [`ParseOptions::max_depth`]. An optimized build parses
+/// 256 levels of structs, maps or arrays within 1 MiB of stack. Unoptimized builds take over ten
+/// kilobytes of stack per level, so they stop at 128 to fit the 2 MiB of a spawned thread.
+///
+pub const DEFAULT_MAX_DEPTH: usize = if cfg!(debug_assertions) { 128 } else { 256 };
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            lenient: false,
+            variants: Variants::default(),
+            keep_some: false,
+            masks: Masks::default(),
+            units: Units::default(),
+            strict_integers: false,
+            serde_json: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+///
+/// [`Variants`] decides what happens to the name of tuple variants and tuple structs such as
+/// `PaymentIntentId("pay_123")` or `my::Enum::Variant(1, 2)`, and of struct variants and structs
+/// such as `Boat::JustStruct { age: 1 }`. The debug output does not tell a struct from a struct
+/// variant, so both are treated alike.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variants {
+    /// The name is dropped and only the inner value (or a vec of values) is kept.
+    #[default]
+    Transparent,
+    /// The value is kept under a single entry map keyed by the last segment of the name.
+    Tagged,
+}
+
+///
+/// [`Masks`] decides what a masked value such as `*** alloc::string::String ***` turns into.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Masks {
+    /// Every masked value becomes the literal `*** masked ***`.
+    #[default]
+    Redact,
+    /// The masked value is kept verbatim, including the type name inside it.
+    Preserve,
+    /// Every masked value becomes the given replacement.
+    Replace(String),
+}
+
+///
+/// [`Units`] decides what a bare unit identifier such as `Succeeded` or `USD` turns into.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Units {
+    /// The identifier becomes a string, `"Succeeded"`.
+    #[default]
+    String,
+    /// The identifier becomes null.
+    Null,
+    /// The identifier becomes a single entry map with a null value, `{ "Succeeded": null }`.
+    Tagged,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Preset keeping as much of the input as possible: struct and variant names, unit variants
+    /// and `Some` wrappers are tagged and masks are kept verbatim. The json serialization of a
+    /// model parsed this way parses back into an equal model, except for the non finite floats
+    /// `NaN`, `inf` and `-inf` which json can only hold as strings and so come back as strings.
+    ///
+    pub fn lossless() -> Self {
+        Self::new()
+            .variants(Variants::Tagged)
+            .keep_some(true)
+            .units(Units::Tagged)
+            .masks(Masks::Preserve)
+    }
+
+    ///
+    /// In lenient mode a value that none of the parsers accept is captured verbatim as a string,
+    /// up to the next top-level `,`, `}`, `)` or `]`, instead of failing the whole parse. Input
+    /// nested deeper than [`ParseOptions::max_depth`] still fails.
+    ///
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Choose how tuple variants are represented, see [`Variants`].
+    pub fn variants(mut self, variants: Variants) -> Self {
+        self.variants = variants;
+        self
+    }
+
+    /// Keep `Some(value)` as `{ "Some": value }` instead of unwrapping it to `value`.
+    pub fn keep_some(mut self, keep_some: bool) -> Self {
+        self.keep_some = keep_some;
+        self
+    }
+
+    /// Choose what bare unit identifiers turn into, see [`Units`].
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    ///
+    /// In strict integer mode a number with a fractional part, like `1.5`, fails the parse. This
+    /// is a validation aid for dumps where every number is expected to be an integer.
+    ///
+    pub fn strict_integers(mut self, strict_integers: bool) -> Self {
+        self.strict_integers = strict_integers;
+        self
+    }
+
+    ///
+    /// Treat the input as containing the debug output of [`serde_json::Value`]: the `Number`,
+    /// `String`, `Bool` and `Object` wrappers are always unwrapped, even with
+    /// [`Variants::Tagged`], and a bare `Null` becomes null.
+    ///
+    pub fn serde_json(mut self, serde_json: bool) -> Self {
+        self.serde_json = serde_json;
+        self
+    }
+
+    /// Choose what masked values turn into, see [`Masks`].
+    pub fn masks(mut self, masks: Masks) -> Self {
+        self.masks = masks;
+        self
+    }
+
+    ///
+    /// Deepest nesting of values accepted, input nested any deeper fails the parse instead of
+    /// overflowing the stack. Every value counts as one level, so `[[1]]` is three deep.
+    ///
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<ParseOptions> = RefCell::new(ParseOptions::default());
+}
+
+struct Restore(Option<ParseOptions>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            CURRENT.with(|current| current.replace(previous));
+        }
+    }
+}
+
+/// Run `f` with `options` installed as the options seen by the combinators.
+pub(crate) fn with_options<R>(options: &ParseOptions, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(Some(
+        CURRENT.with(|current| current.replace(options.clone())),
+    ));
+    f()
+}
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static EXCEEDED: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Ascend(usize);
+
+impl Drop for Ascend {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(self.0));
+    }
+}
+
+/// Run `f` one nesting level deeper, nothing when that goes past [`ParseOptions::max_depth`].
+pub(crate) fn nested<R>(f: impl FnOnce() -> R) -> Option<R> {
+    let depth = DEPTH.with(Cell::get);
+    if depth == 0 {
+        EXCEEDED.with(|exceeded| exceeded.set(false));
+    }
+    if depth >= current(|options| options.max_depth) {
+        EXCEEDED.with(|exceeded| exceeded.set(true));
+        return None;
+    }
+
+    DEPTH.with(|current| current.set(depth + 1));
+    let _ascend = Ascend(depth);
+    Some(f())
+}
+
+///
+/// Whether the parse running on this thread went past [`ParseOptions::max_depth`], the failure
+/// is then final and lenient mode does not recover from it.
+///
+pub(crate) fn exceeded() -> bool {
+    EXCEEDED.with(Cell::get)
+}
+
+/// Read the options installed for the parse currently running on this thread.
+pub(crate) fn current<R>(f: impl FnOnce(&ParseOptions) -> R) -> R {
+    CURRENT.with(|current| f(&current.borrow()))
+}
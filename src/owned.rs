@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+
+use crate::DataModel;
+
+///
+/// [`OwnedDataModel`] mirrors [`DataModel`] without borrowing from the input, so a parsed tree
+/// can outlive the buffer it was parsed from. Obtained with [`DataModel::into_owned`].
+///
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(untagged)]
+pub enum OwnedDataModel {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(#[serde(serialize_with = "crate::serialize_float")] f64),
+    String(String),
+    Map(IndexMap<String, OwnedDataModel>),
+    Vec(Vec<OwnedDataModel>),
+}
+
+impl<'a> DataModel<'a> {
+    /// Deep copy the tree into an [`OwnedDataModel`], owning every key and string.
+    pub fn into_owned(self) -> OwnedDataModel {
+        match self {
+            DataModel::Null => OwnedDataModel::Null,
+            DataModel::Boolean(data) => OwnedDataModel::Boolean(data),
+            DataModel::Integer(data) => OwnedDataModel::Integer(data),
+            DataModel::Float(data) => OwnedDataModel::Float(data),
+            DataModel::String(data) => OwnedDataModel::String(data.into_owned()),
+            DataModel::Map(map) => OwnedDataModel::Map(
+                map.into_iter()
+                    .map(|(key, value)| (key.to_string(), value.into_owned()))
+                    .collect(),
+            ),
+            DataModel::Vec(vec) => {
+                OwnedDataModel::Vec(vec.into_iter().map(DataModel::into_owned).collect())
+            }
+        }
+    }
+}
+
+impl OwnedDataModel {
+    /// Borrow the tree as a [`DataModel`], keys and strings point into `self` instead of a copy.
+    pub fn as_data_model(&self) -> DataModel<'_> {
+        match self {
+            OwnedDataModel::Null => DataModel::Null,
+            OwnedDataModel::Boolean(data) => DataModel::Boolean(*data),
+            OwnedDataModel::Integer(data) => DataModel::Integer(*data),
+            OwnedDataModel::Float(data) => DataModel::Float(*data),
+            OwnedDataModel::String(data) => DataModel::String(Cow::Borrowed(data)),
+            OwnedDataModel::Map(map) => DataModel::Map(
+                map.iter()
+                    .map(|(key, value)| (key.as_str(), value.as_data_model()))
+                    .collect(),
+            ),
+            OwnedDataModel::Vec(vec) => {
+                DataModel::Vec(vec.iter().map(OwnedDataModel::as_data_model).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+
+    use super::OwnedDataModel;
+    use crate::root;
+
+    fn parse_owned(data: &str) -> OwnedDataModel {
+        let buffer = data.to_string();
+        root::<(&str, ErrorKind)>(&buffer).unwrap().1.into_owned()
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let owned = parse_owned(
+            r#"Outer { id: 1, inner: [Inner { name: "a\"b", tags: [[1.5], []] }], none: None, on: true }"#,
+        );
+
+        assert_eq!(
+            owned,
+            OwnedDataModel::Map(
+                [
+                    ("id".to_string(), OwnedDataModel::Integer(1)),
+                    (
+                        "inner".to_string(),
+                        OwnedDataModel::Vec(vec![OwnedDataModel::Map(
+                            [
+                                (
+                                    "name".to_string(),
+                                    OwnedDataModel::String("a\"b".to_string())
+                                ),
+                                (
+                                    "tags".to_string(),
+                                    OwnedDataModel::Vec(vec![
+                                        OwnedDataModel::Vec(vec![OwnedDataModel::Float(1.5)]),
+                                        OwnedDataModel::Vec(vec![]),
+                                    ])
+                                ),
+                            ]
+                            .into()
+                        )])
+                    ),
+                    ("none".to_string(), OwnedDataModel::Null),
+                    ("on".to_string(), OwnedDataModel::Boolean(true)),
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            serde_json::to_string(&owned).unwrap(),
+            r#"{"id":1,"inner":[{"name":"a\"b","tags":[[1.5],[]]}],"none":null,"on":true}"#
+        );
+    }
+
+    #[test]
+    fn test_as_data_model() {
+        let data = r#"Outer { id: 1, inner: [Inner { name: "a\"b", rate: 0.5 }], none: None }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(parse.clone().into_owned().as_data_model(), parse);
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::DataModel;
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Render the tree as pretty printed RON. Tagged values, single entry maps keyed by a
+    /// capitalized name as produced by [`crate::Variants::Tagged`], are written with the enum
+    /// syntax `Variant(value)`, or `Variant` when the value is null. Parsing the output back with
+    /// the same options gives an equal model.
+    ///
+    pub fn to_pretty_ron(&self) -> String {
+        let mut output = String::new();
+        write_ron(self, 0, &mut output);
+        output
+    }
+}
+
+fn write_ron(node: &DataModel<'_>, indent: usize, output: &mut String) {
+    let pad = |indent: usize| "    ".repeat(indent);
+
+    match node {
+        DataModel::Null => output.push_str("None"),
+        DataModel::Boolean(data) => output.push_str(&data.to_string()),
+        DataModel::Integer(data) => output.push_str(&data.to_string()),
+        DataModel::Float(data) => output.push_str(&format!("{:?}", data)),
+        DataModel::String(data) => write_string(data, output),
+        DataModel::Map(map) if map.is_empty() => output.push_str("{}"),
+        DataModel::Map(map) => match map.iter().next() {
+            Some((name, DataModel::Null)) if is_tag(map) => output.push_str(name),
+            Some((name, value)) if is_tag(map) => {
+                output.push_str(name);
+                output.push('(');
+                write_ron(value, indent, output);
+                output.push(')');
+            }
+            _ => {
+                output.push_str("{\n");
+                for (key, value) in map {
+                    let _ = write!(output, "{}\"{}\": ", pad(indent + 1), key);
+                    write_ron(value, indent + 1, output);
+                    output.push_str(",\n");
+                }
+                output.push_str(&pad(indent));
+                output.push('}');
+            }
+        },
+        DataModel::Vec(vec) if vec.is_empty() => output.push_str("[]"),
+        DataModel::Vec(vec) => {
+            output.push_str("[\n");
+            for value in vec {
+                output.push_str(&pad(indent + 1));
+                write_ron(value, indent + 1, output);
+                output.push_str(",\n");
+            }
+            output.push_str(&pad(indent));
+            output.push(']');
+        }
+    }
+}
+
+fn is_tag(map: &IndexMap<&str, DataModel<'_>>) -> bool {
+    map.len() == 1
+        && map
+            .keys()
+            .all(|key| key.starts_with(|item: char| item.is_ascii_uppercase()))
+}
+
+fn write_string(data: &str, output: &mut String) {
+    output.push('"');
+    for item in data.chars() {
+        match item {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\0' => output.push_str("\\0"),
+            item if item.is_control() => {
+                let _ = write!(output, "\\u{{{:x}}}", item as u32);
+            }
+            item => output.push(item),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+
+    use crate::{root_with, ParseOptions, Units, Variants};
+
+    #[test]
+    fn test_to_pretty_ron() {
+        let data = r#"Payment { id: PaymentId("pay_1"), status: Succeeded, amount: Some(Value(65.5)), tags: ["a\"b", "line\nbreak"], meta: {}, attempts: [] }"#;
+        let options = ParseOptions::new()
+            .variants(Variants::Tagged)
+            .keep_some(true)
+            .units(Units::Tagged);
+        let parse = root_with::<(&str, ErrorKind)>(data, &options).unwrap().1;
+
+        let ron = parse.to_pretty_ron();
+        assert_eq!(
+            ron,
+            r#"Payment({
+    "id": PaymentId("pay_1"),
+    "status": Succeeded,
+    "amount": Some(Value(65.5)),
+    "tags": [
+        "a\"b",
+        "line\nbreak",
+    ],
+    "meta": {},
+    "attempts": [],
+})"#
+        );
+
+        let (rest, round_trip) = root_with::<(&str, ErrorKind)>(&ron, &options).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(round_trip, parse);
+    }
+}
@@ -0,0 +1,223 @@
+use std::borrow::Cow;
+
+use crate::{traverse::join_path, DataModel};
+
+///
+/// [`Warning`] reports a value that [`DataModel::coerce_to_schema`] could not bring in line with
+/// the schema, the value is kept as it was.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// Dotted path of the value, see [`DataModel::find`] for the format.
+    pub path: String,
+    pub message: String,
+}
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Coerce the value, on a best-effort basis, to the shape of `schema`. The schema is an
+    /// example value: a string, integer, float or boolean asks for that scalar type, a map for a
+    /// map with the listed keys, a vec for an array whose elements follow its first element, and
+    /// null accepts anything.
+    ///
+    /// Numbers and booleans are stringified where a string is expected, strings holding a number
+    /// or a boolean are parsed back, whole floats within the [`i64`] range become integers where
+    /// an integer is expected and a single value is wrapped into an array where an array is
+    /// expected. Null is always kept and map keys missing from the schema are left untouched.
+    ///
+    pub fn coerce_to_schema(&self, schema: &DataModel<'_>) -> (DataModel<'a>, Vec<Warning>) {
+        let mut warnings = Vec::new();
+        let value = coerce(self, schema, String::new(), &mut warnings);
+        (value, warnings)
+    }
+}
+
+fn coerce<'a>(
+    value: &DataModel<'a>,
+    schema: &DataModel<'_>,
+    path: String,
+    warnings: &mut Vec<Warning>,
+) -> DataModel<'a> {
+    match (schema, value) {
+        (DataModel::Null, _) | (_, DataModel::Null) => value.clone(),
+        (DataModel::String(_), DataModel::String(_))
+        | (DataModel::Integer(_), DataModel::Integer(_))
+        | (DataModel::Float(_), DataModel::Float(_) | DataModel::Integer(_))
+        | (DataModel::Boolean(_), DataModel::Boolean(_)) => value.clone(),
+        (DataModel::Integer(_), DataModel::Float(data))
+            if data.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(data) =>
+        {
+            DataModel::Integer(*data as i64)
+        }
+        (DataModel::String(_), DataModel::Integer(data)) => {
+            DataModel::String(Cow::Owned(data.to_string()))
+        }
+        (DataModel::String(_), DataModel::Float(data)) => {
+            DataModel::String(Cow::Owned(data.to_string()))
+        }
+        (DataModel::String(_), DataModel::Boolean(data)) => {
+            DataModel::String(Cow::Owned(data.to_string()))
+        }
+        (DataModel::Integer(_), DataModel::String(data)) => match data.trim().parse() {
+            Ok(data) => DataModel::Integer(data),
+            Err(_) => mismatch(value, "integer", path, warnings),
+        },
+        (DataModel::Float(_), DataModel::String(data)) => match data.trim().parse() {
+            Ok(data) => DataModel::Float(data),
+            Err(_) => mismatch(value, "number", path, warnings),
+        },
+        (DataModel::Boolean(_), DataModel::String(data)) => match data.trim().parse() {
+            Ok(data) => DataModel::Boolean(data),
+            Err(_) => mismatch(value, "boolean", path, warnings),
+        },
+        (DataModel::Vec(schema), DataModel::Vec(vec)) => DataModel::Vec(
+            vec.iter()
+                .enumerate()
+                .map(|(index, element)| match schema.first() {
+                    Some(schema) => coerce(
+                        element,
+                        schema,
+                        join_path(&path, &index.to_string()),
+                        warnings,
+                    ),
+                    None => element.clone(),
+                })
+                .collect(),
+        ),
+        (DataModel::Vec(schema), _) => DataModel::Vec(vec![match schema.first() {
+            Some(schema) => coerce(value, schema, join_path(&path, "0"), warnings),
+            None => value.clone(),
+        }]),
+        (DataModel::Map(schema), DataModel::Map(map)) => DataModel::Map(
+            map.iter()
+                .map(|(key, element)| {
+                    let element = match schema.get(key) {
+                        Some(schema) => coerce(element, schema, join_path(&path, key), warnings),
+                        None => element.clone(),
+                    };
+                    (*key, element)
+                })
+                .collect(),
+        ),
+        (DataModel::String(_), _) => mismatch(value, "string", path, warnings),
+        (DataModel::Integer(_), _) => mismatch(value, "integer", path, warnings),
+        (DataModel::Float(_), _) => mismatch(value, "number", path, warnings),
+        (DataModel::Boolean(_), _) => mismatch(value, "boolean", path, warnings),
+        (DataModel::Map(_), _) => mismatch(value, "map", path, warnings),
+    }
+}
+
+fn mismatch<'a>(
+    value: &DataModel<'a>,
+    expected: &str,
+    path: String,
+    warnings: &mut Vec<Warning>,
+) -> DataModel<'a> {
+    let found = match value {
+        DataModel::Null => "null",
+        DataModel::Boolean(_) => "boolean",
+        DataModel::Integer(_) => "integer",
+        DataModel::Float(_) => "number",
+        DataModel::String(_) => "string",
+        DataModel::Map(_) => "map",
+        DataModel::Vec(_) => "array",
+    };
+    warnings.push(Warning {
+        path,
+        message: format!("expected {}, found {}", expected, found),
+    });
+    value.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+
+    use super::Warning;
+    use crate::{root, DataModel};
+
+    #[test]
+    fn test_coerce_to_schema() {
+        let data = r#"{ "tags": "a" }"#;
+        let schema = r#"{ "tags": ["tag"] }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        let schema = root::<(&str, ErrorKind)>(schema).unwrap().1;
+
+        assert_eq!(
+            parse.coerce_to_schema(&schema),
+            (
+                DataModel::Map(
+                    [("tags", DataModel::Vec(vec![DataModel::String("a".into())]))].into()
+                ),
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_schema_warnings() {
+        let data = r#"Order { id: 42, paid: "true", amount: "ten", items: [{ "sku": 1 }, 2], note: None }"#;
+        let schema =
+            r#"{ "id": "", "paid": false, "amount": 0, "items": [{ "sku": "" }], "note": "" }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        let schema = root::<(&str, ErrorKind)>(schema).unwrap().1;
+
+        let (value, warnings) = parse.coerce_to_schema(&schema);
+        assert_eq!(
+            value,
+            DataModel::Map(
+                [
+                    ("id", DataModel::String("42".into())),
+                    ("paid", DataModel::Boolean(true)),
+                    ("amount", DataModel::String("ten".into())),
+                    (
+                        "items",
+                        DataModel::Vec(vec![
+                            DataModel::Map([("sku", DataModel::String("1".into()))].into()),
+                            DataModel::Integer(2)
+                        ])
+                    ),
+                    ("note", DataModel::Null),
+                ]
+                .into()
+            )
+        );
+
+        assert_eq!(
+            warnings,
+            vec![
+                Warning {
+                    path: "amount".to_string(),
+                    message: "expected integer, found string".to_string()
+                },
+                Warning {
+                    path: "items.1".to_string(),
+                    message: "expected map, found integer".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_schema_float_range() {
+        let data = r#"{ "small": 2.0, "huge": 1e300, "half": 2.5 }"#;
+        let schema = r#"{ "small": 0, "huge": 0, "half": 0 }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        let schema = root::<(&str, ErrorKind)>(schema).unwrap().1;
+
+        let (value, warnings) = parse.coerce_to_schema(&schema);
+        assert_eq!(value["small"], DataModel::Integer(2));
+        assert_eq!(value["huge"], DataModel::Float(1e300));
+        assert_eq!(value["half"], DataModel::Float(2.5));
+        assert_eq!(
+            warnings
+                .iter()
+                .map(|warning| warning.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["huge", "half"]
+        );
+        assert_eq!(warnings[0].message, "expected integer, found number");
+    }
+}
@@ -0,0 +1,379 @@
+use std::io::BufRead;
+
+use nom::{
+    error::{ContextError, ErrorKind, FromExternalError, ParseError},
+    IResult, Needed,
+};
+
+use crate::{root_with, string, DataModel, OwnedDataModel, ParseOptions};
+
+///
+/// [`StreamParser`] accumulates a single debug record arriving in pieces, e.g. a `{:#?}` dump
+/// read from a socket line by line.
+///
+/// Brackets and string, raw string and char literals are tracked as the bytes arrive, so each
+/// byte is only looked at once. The record is parsed when its outermost bracket or quote
+/// closes, or, for a bare value such as `42`, at a newline or at [`StreamParser::finish`]. The
+/// record is complete once that parse accepts the whole buffer, the tree is then kept for
+/// [`StreamParser::parse`], which reports [`nom::Err::Incomplete`] until then.
+///
+#[derive(Debug, Default)]
+pub struct StreamParser {
+    buffer: String,
+    options: ParseOptions,
+    finished: bool,
+    scanned: usize,
+    depth: usize,
+    bracketed: bool,
+    literal: Option<Literal>,
+    parsed: Option<OwnedDataModel>,
+}
+
+/// The literal open at the end of the scanned input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Literal {
+    String { escaping: bool },
+    Raw { hashes: usize },
+}
+
+/// Length of the longest char literal, `'\u{10ffff}'`.
+const MAX_CHAR_LEN: usize = 12;
+
+impl StreamParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same as [`StreamParser::new`] but parsing the record with the provided [`ParseOptions`].
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    ///
+    /// Append `chunk` to the buffer, returning `true` once the record is complete.
+    ///
+    pub fn feed(&mut self, chunk: &str) -> bool {
+        self.buffer.push_str(chunk);
+        self.check();
+        self.is_complete()
+    }
+
+    ///
+    /// Mark the end of the input, so that a bare value not followed by a newline, such as the
+    /// `42` of a buffer holding only that, is complete as well. Returns whether the record is
+    /// complete.
+    ///
+    pub fn finish(&mut self) -> bool {
+        self.finished = true;
+        self.check();
+        self.is_complete()
+    }
+
+    /// Whether the record is complete, see [`StreamParser`].
+    pub fn is_complete(&self) -> bool {
+        self.parsed.is_some()
+    }
+
+    fn check(&mut self) {
+        if self.is_complete() {
+            return;
+        }
+
+        let closed = self.scan();
+        let settled = self.scanned == self.buffer.len() && self.literal.is_none();
+        // a bare value could still grow until the line or the input ends
+        let bare = !self.bracketed && (self.finished || self.buffer.ends_with('\n'));
+        if !settled || self.depth != 0 || !(closed || bare) {
+            return;
+        }
+
+        let record = self.buffer.trim_end();
+        if let Ok(("", tree)) = root_with::<(&str, ErrorKind)>(record, &self.options) {
+            self.parsed = Some(tree.into_owned());
+        }
+    }
+
+    ///
+    /// Advance over the bytes not scanned yet, tracking the nesting of brackets and the open
+    /// literal. Stops early at a char literal or the end of a raw string that is not fully
+    /// buffered yet. Returns whether the outermost bracket or literal closed along the way.
+    ///
+    fn scan(&mut self) -> bool {
+        let bytes = self.buffer.as_bytes();
+        let mut closed = false;
+        let mut index = self.scanned;
+
+        while index < bytes.len() {
+            let byte = bytes[index];
+            match self.literal {
+                Some(Literal::String { escaping }) => {
+                    self.literal = match (escaping, byte) {
+                        (true, _) => Some(Literal::String { escaping: false }),
+                        (false, b'\\') => Some(Literal::String { escaping: true }),
+                        (false, b'"') => {
+                            closed |= self.depth == 0;
+                            None
+                        }
+                        _ => self.literal,
+                    };
+                }
+                Some(Literal::Raw { hashes }) if byte == b'"' => {
+                    let rest = &bytes[index + 1..];
+                    if rest.len() < hashes && !self.finished {
+                        break;
+                    }
+                    if rest.len() >= hashes && rest[..hashes].iter().all(|item| *item == b'#') {
+                        closed |= self.depth == 0;
+                        self.literal = None;
+                        index += hashes;
+                    }
+                }
+                Some(Literal::Raw { .. }) => {}
+                None => match byte {
+                    b'"' => {
+                        // the `r`, `br` and hashes of a raw string come right before its quote
+                        let hashes = bytes[..index]
+                            .iter()
+                            .rev()
+                            .take_while(|item| **item == b'#')
+                            .count();
+                        let prefix = &bytes[..index - hashes];
+                        let raw = prefix.strip_suffix(b"r").is_some_and(|prefix| {
+                            !prefix
+                                .strip_suffix(b"b")
+                                .unwrap_or(prefix)
+                                .last()
+                                .is_some_and(|item| item.is_ascii_alphanumeric() || *item == b'_')
+                        });
+                        self.literal = Some(match raw {
+                            true => Literal::Raw { hashes },
+                            false => Literal::String { escaping: false },
+                        });
+                    }
+                    b'\'' => match string::parse_char::<(&str, ErrorKind)>(&self.buffer[index..]) {
+                        Ok((rest, _)) => {
+                            closed |= self.depth == 0;
+                            index = self.buffer.len() - rest.len();
+                            continue;
+                        }
+                        // not a char literal yet, or a lifetime like the `'a` of a literal
+                        Err(_) if !self.finished && bytes.len() - index < MAX_CHAR_LEN => break,
+                        Err(_) => {}
+                    },
+                    b'(' | b'[' | b'{' => {
+                        self.bracketed = true;
+                        self.depth += 1;
+                    }
+                    b')' | b']' | b'}' => {
+                        self.depth = self.depth.saturating_sub(1);
+                        closed |= self.depth == 0;
+                    }
+                    _ => {}
+                },
+            }
+            index += 1;
+        }
+
+        self.scanned = index;
+        closed
+    }
+
+    ///
+    /// Read lines from `reader` until the record is complete or the reader is exhausted,
+    /// returning whether the record is complete.
+    ///
+    pub fn read_from<R: BufRead>(&mut self, mut reader: R) -> std::io::Result<bool> {
+        let mut line = String::new();
+
+        while !self.is_complete() {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(self.finish());
+            }
+            self.feed(&line);
+        }
+
+        Ok(self.is_complete())
+    }
+
+    ///
+    /// The record parsed with [`root_with`] and the options of the stream once it is complete,
+    /// [`nom::Err::Incomplete`] while more input is needed. The tree is kept from the parse that
+    /// completed the record rather than parsed again.
+    ///
+    pub fn parse<
+        'a,
+        E: ParseError<&'a str>
+            + ContextError<&'a str>
+            + FromExternalError<&'a str, std::num::ParseIntError>
+            + std::fmt::Debug,
+    >(
+        &'a self,
+    ) -> IResult<&'a str, DataModel<'a>, E> {
+        match &self.parsed {
+            Some(tree) => Ok(("", tree.as_data_model())),
+            None => Err(nom::Err::Incomplete(Needed::Unknown)),
+        }
+    }
+
+    /// The input buffered so far.
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+
+    use crate::*;
+
+    #[test]
+    fn test_stream_chunks() {
+        let record = format!(
+            "Dalton {{ name: \"{}\", tags: [\"a\", \"b\"], nested: Inner {{ value: 1 }} }}",
+            "A".repeat(10_000)
+        );
+
+        let mut parser = StreamParser::new();
+        let mut completions = 0;
+
+        for chunk in record.as_bytes().chunks(7) {
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            if parser.feed(chunk) {
+                completions += 1;
+            } else {
+                assert!(matches!(
+                    parser.parse::<(&str, ErrorKind)>(),
+                    Err(nom::Err::Incomplete(_))
+                ));
+            }
+        }
+
+        assert_eq!(completions, 1);
+
+        let parse = parser.parse::<(&str, ErrorKind)>().unwrap();
+        assert_eq!(parse.0, "");
+        assert_eq!(parse.1.count_keys(), 4);
+    }
+
+    #[test]
+    fn test_stream_reader() {
+        let record = "Bob {\n    inner_int: 123.0,\n    inner_string: \"da}ta\"\n}\n";
+
+        let mut parser = StreamParser::new();
+        assert!(parser.read_from(record.as_bytes()).unwrap());
+
+        let parse = parser.parse::<(&str, ErrorKind)>().unwrap().1;
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("inner_int", DataModel::Float(123.0)),
+                    ("inner_string", DataModel::String("da}ta".into())),
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_stream_literals() {
+        let record = r##"Token { quote: '"', open: '(', brace: '{', raw: r#"a"(b"#, value: 1 }"##;
+
+        let mut parser = StreamParser::new();
+        let completions = record
+            .as_bytes()
+            .chunks(5)
+            .filter(|chunk| parser.feed(std::str::from_utf8(chunk).unwrap()))
+            .count();
+        assert_eq!(completions, 1);
+
+        let parse = parser.parse::<(&str, ErrorKind)>().unwrap();
+        assert_eq!(parse.0, "");
+        assert_eq!(parse.1["raw"], DataModel::String("a\"(b".into()));
+    }
+
+    #[test]
+    fn test_stream_lines() {
+        let items = (0..5_000)
+            .map(|id| {
+                format!(
+                    "        Item {{\n            id: {},\n            tags: [],\n        }},\n",
+                    id
+                )
+            })
+            .collect::<String>();
+        let record = format!("Dump {{\n    items: [\n{}    ],\n}}\n", items);
+
+        let mut parser = StreamParser::new();
+        let completions = record
+            .split_inclusive('\n')
+            .filter(|line| parser.feed(line))
+            .count();
+        assert_eq!(completions, 1);
+
+        let parse = parser.parse::<(&str, ErrorKind)>().unwrap().1;
+        assert_eq!(parse["items"].vec_or_empty().len(), 5_000);
+    }
+
+    #[test]
+    fn test_stream_split_literals() {
+        let mut parser = StreamParser::new();
+        assert!(!parser.feed("Raw { s: r#\"x\""));
+        assert!(!parser.feed("\"#"));
+        assert!(!parser.feed(", c: '"));
+        assert!(!parser.feed("}"));
+        assert!(parser.feed("' }"));
+        assert_eq!(
+            parser.parse::<(&str, ErrorKind)>().unwrap().1,
+            DataModel::Map(
+                [
+                    ("s", DataModel::String("x\"".into())),
+                    ("c", DataModel::String("}".into()))
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_stream_scalar() {
+        let mut parser = StreamParser::new();
+        assert!(!parser.feed("4"));
+        assert!(!parser.feed("2"));
+        assert!(parser.feed("\n"));
+        assert_eq!(
+            parser.parse::<(&str, ErrorKind)>().unwrap(),
+            ("", DataModel::Integer(42))
+        );
+
+        let mut parser = StreamParser::new();
+        assert!(parser.read_from("None".as_bytes()).unwrap());
+        assert_eq!(
+            parser.parse::<(&str, ErrorKind)>().unwrap().1,
+            DataModel::Null
+        );
+
+        let mut parser = StreamParser::new();
+        assert!(!parser.feed("Paid "));
+        assert!(!parser.feed("{ id"));
+        assert!(parser.feed(": 1 }"));
+    }
+
+    #[test]
+    fn test_stream_options() {
+        let options = ParseOptions::new().variants(Variants::Tagged);
+        let mut parser = StreamParser::with_options(options);
+        assert!(parser.feed(r#"PaymentId("pay_1")"#));
+        assert_eq!(
+            parser.parse::<(&str, ErrorKind)>().unwrap().1,
+            DataModel::Map([("PaymentId", DataModel::String("pay_1".into()))].into())
+        );
+    }
+}
@@ -5,6 +5,9 @@ use nom::combinator::{map, map_opt, map_res, value, verify};
 use nom::error::{FromExternalError, ParseError};
 use nom::multi::fold_many0;
 use nom::sequence::{delimited, preceded};
+// the char, raw and byte literals are tried on a whole input, running out of it is an error
+// there rather than a request for more
+use nom::{bytes::complete as bytes, character::complete as character};
 use nom::{IResult, Parser};
 
 fn parse_unicode<'a, E>(input: &'a str) -> IResult<&'a str, char, E>
@@ -13,14 +16,21 @@ where
 {
     let parse_hex = take_while_m_n(1, 6, |c: char| c.is_ascii_hexdigit());
 
-    let parse_delimited_hex = preceded(char('u'), delimited(char('{'), parse_hex, char('}')));
+    // `\u{1F}` as printed by `Debug`, or `\u001F` as written in json
+    let parse_delimited_hex = preceded(
+        char('u'),
+        alt((
+            delimited(char('{'), parse_hex, char('}')),
+            take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+        )),
+    );
 
     let parse_u32 = map_res(parse_delimited_hex, move |hex| u32::from_str_radix(hex, 16));
 
     map_opt(parse_u32, std::char::from_u32).parse(input)
 }
 
-/// Parse an escaped character: \n, \t, \r, \u{00AC}, etc.
+/// Parse an escaped character: \n, \t, \r, \0, \u{00AC}, \u00AC, etc.
 fn parse_escaped_char<'a, E>(input: &'a str) -> IResult<&'a str, char, E>
 where
     E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
@@ -32,11 +42,13 @@ where
             value('\n', char('n')),
             value('\r', char('r')),
             value('\t', char('t')),
+            value('\0', char('0')),
             value('\u{08}', char('b')),
             value('\u{0C}', char('f')),
             value('\\', char('\\')),
             value('/', char('/')),
             value('"', char('"')),
+            value('\'', char('\'')),
         )),
     )
     .parse(input)
@@ -98,3 +110,99 @@ where
 
     delimited(char('"'), build_string, char('"')).parse(input)
 }
+
+/// Parse a char literal as printed by `Debug`: `'a'`, `'\''`, `'\n'` or `'\u{1f600}'`.
+pub fn parse_char<'a, E>(input: &'a str) -> IResult<&'a str, char, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+{
+    delimited(
+        character::char('\''),
+        alt((parse_escaped_char, character::none_of("\\'"))),
+        character::char('\''),
+    )
+    .parse(input)
+}
+
+///
+/// Parse a raw string `r"..."`, `r#"..."#` or with any number of `#`, up to the closing quote
+/// followed by as many `#`. The content is kept verbatim, escapes are not processed.
+///
+pub fn parse_raw_string<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, &'a str, E> {
+    let (rest, hashes) = delimited(
+        character::char('r'),
+        bytes::take_while(|item| item == '#'),
+        character::char('"'),
+    )(input)?;
+
+    let closing = format!("\"{}", hashes);
+    match rest.find(&closing) {
+        Some(end) => Ok((&rest[end + closing.len()..], &rest[..end])),
+        None => Err(nom::Err::Error(E::from_error_kind(
+            rest,
+            nom::error::ErrorKind::TakeUntil,
+        ))),
+    }
+}
+
+/// A fragment of a byte string: a run of literal characters or a single escaped byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteFragment<'a> {
+    Literal(&'a str),
+    Byte(u8),
+}
+
+/// Parse an escaped byte: \x41, \n, \t, \r, \0, \\, \' or \".
+fn parse_escaped_byte<'a, E>(input: &'a str) -> IResult<&'a str, u8, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+{
+    let parse_hex = bytes::take_while_m_n(2, 2, |c: char| c.is_ascii_hexdigit());
+
+    preceded(
+        character::char('\\'),
+        alt((
+            map_res(preceded(character::char('x'), parse_hex), |hex| {
+                u8::from_str_radix(hex, 16)
+            }),
+            value(b'\n', character::char('n')),
+            value(b'\r', character::char('r')),
+            value(b'\t', character::char('t')),
+            value(b'\0', character::char('0')),
+            value(b'\\', character::char('\\')),
+            value(b'\'', character::char('\'')),
+            value(b'"', character::char('"')),
+        )),
+    )
+    .parse(input)
+}
+
+///
+/// Parse a byte string such as `b"\x00\x01"` into its bytes, with the `\xNN` escapes decoded.
+/// Characters outside of ASCII contribute their UTF-8 encoding.
+///
+pub fn parse_byte_string<'a, E>(input: &'a str) -> IResult<&'a str, Vec<u8>, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, std::num::ParseIntError>,
+{
+    let literal = verify(bytes::is_not("\"\\"), |s: &str| !s.is_empty());
+    let fragment = alt((
+        map(literal, ByteFragment::Literal),
+        map(parse_escaped_byte, ByteFragment::Byte),
+    ));
+    let build_bytes = fold_many0(fragment, Vec::new, |mut output, fragment| {
+        match fragment {
+            ByteFragment::Literal(s) => output.extend_from_slice(s.as_bytes()),
+            ByteFragment::Byte(b) => output.push(b),
+        }
+        output
+    });
+
+    preceded(
+        character::char('b'),
+        delimited(character::char('"'), build_bytes, character::char('"')),
+    )
+    .parse(input)
+}
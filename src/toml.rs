@@ -0,0 +1,52 @@
+use crate::DataModel;
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Serialize the tree as a TOML document. TOML has no null, so null map entries are left
+    /// out, and it needs a table at the top, so anything other than a map is an error, as is a
+    /// null inside an array.
+    ///
+    pub fn to_toml(&self) -> Result<String, ::toml::ser::Error> {
+        let mut value = self.clone();
+        value.strip_nulls();
+        ::toml::to_string(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+
+    use crate::{root, DataModel};
+
+    #[test]
+    fn test_to_toml() {
+        let data = r#"Config { name: "payments", port: 8080, ratio: 0.5, debug: false, proxy: None, hosts: ["a", "b"], db: Db { user: "root", pool: Some(4) } }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse.to_toml().unwrap(),
+            r#"name = "payments"
+port = 8080
+ratio = 0.5
+debug = false
+hosts = ["a", "b"]
+
+[db]
+user = "root"
+pool = 4
+"#
+        );
+
+        assert!(DataModel::Vec(vec![DataModel::Integer(1)])
+            .to_toml()
+            .is_err());
+        assert!(
+            DataModel::Map([("a", DataModel::Vec(vec![DataModel::Null]))].into())
+                .to_toml()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,933 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use indexmap::IndexMap;
+
+use crate::{traverse::resolve, DataModel, TRUNCATION_MARKER};
+
+///
+/// [`NormalizeOptions`] selects the passes run by [`DataModel::normalize`], every pass but
+/// [`NormalizeOptions::unwrap_tags`] is enabled by default.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    unwrap_tags: bool,
+    strip_nulls: bool,
+    integral_floats: bool,
+    sort_keys: bool,
+    unit_variants: Vec<String>,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            unwrap_tags: false,
+            strip_nulls: true,
+            integral_floats: true,
+            sort_keys: true,
+            unit_variants: Vec::new(),
+        }
+    }
+}
+
+impl NormalizeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Replace tagged values like `{ "Some": value }` with the value, see [`DataModel::unwrap_tags`].
+    /// Off by default since it is meant for input parsed with [`crate::Variants::Tagged`] or
+    /// [`crate::Units::Tagged`], a real map with a single key like `{"USD": 5}` looks the same.
+    ///
+    pub fn unwrap_tags(mut self, unwrap_tags: bool) -> Self {
+        self.unwrap_tags = unwrap_tags;
+        self
+    }
+
+    /// Remove null map entries, see [`DataModel::strip_nulls`].
+    pub fn strip_nulls(mut self, strip_nulls: bool) -> Self {
+        self.strip_nulls = strip_nulls;
+        self
+    }
+
+    /// Turn whole floats like `50.0` into integers, see [`DataModel::integral_floats`].
+    pub fn integral_floats(mut self, integral_floats: bool) -> Self {
+        self.integral_floats = integral_floats;
+        self
+    }
+
+    /// Order map entries by key instead of input order, see [`DataModel::sort_keys`].
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    ///
+    /// Unit variants, like `Succeeded`, that should read the same whether they were printed bare
+    /// or quoted, see [`DataModel::unify_unit_variants`]. None by default.
+    ///
+    pub fn unit_variants(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.unit_variants = names.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Apply `patch` following the json merge patch semantics of RFC 7386: maps are merged
+    /// recursively, a null in the patch removes the key and any other value replaces the target.
+    ///
+    pub fn apply_patch(&mut self, patch: &DataModel<'a>) {
+        let DataModel::Map(patch) = patch else {
+            *self = patch.clone();
+            return;
+        };
+
+        if !matches!(self, DataModel::Map(_)) {
+            *self = DataModel::Map(IndexMap::new());
+        }
+
+        if let DataModel::Map(target) = self {
+            for (key, value) in patch {
+                match value {
+                    DataModel::Null => {
+                        target.shift_remove(key);
+                    }
+                    _ => target
+                        .entry(key)
+                        .or_insert(DataModel::Null)
+                        .apply_patch(value),
+                }
+            }
+        }
+    }
+
+    ///
+    /// Merge the vecs `a` and `b` of maps, upserting by the value of `key`: an element of `b`
+    /// whose `key` matches an element of `a` has its entries merged over it, any other element of
+    /// `b` is appended. When either side is not a vec the result is `b`.
+    ///
+    pub fn merge_arrays_by_key(a: &DataModel<'a>, b: &DataModel<'a>, key: &str) -> DataModel<'a> {
+        let (DataModel::Vec(a), DataModel::Vec(b)) = (a, b) else {
+            return b.clone();
+        };
+
+        let mut merged = a.clone();
+        for element in b {
+            let id = match element {
+                DataModel::Map(map) => map.get(key),
+                _ => None,
+            };
+            let target = id.and_then(|id| {
+                merged.iter_mut().find_map(|target| match target {
+                    DataModel::Map(target) if target.get(key) == Some(id) => Some(target),
+                    _ => None,
+                })
+            });
+
+            match (target, element) {
+                (Some(target), DataModel::Map(map)) => {
+                    target.extend(map.iter().map(|(key, value)| (*key, value.clone())))
+                }
+                _ => merged.push(element.clone()),
+            }
+        }
+
+        DataModel::Vec(merged)
+    }
+
+    ///
+    /// Shorten every string longer than `max` characters to its first `max` characters followed
+    /// by `ellipsis`.
+    ///
+    pub fn truncate_strings(&mut self, max: usize, ellipsis: &str) {
+        self.walk_mut(|node| {
+            if let DataModel::String(data) = node {
+                if let Some((index, _)) = data.char_indices().nth(max) {
+                    *data = Cow::Owned(format!("{}{}", &data[..index], ellipsis));
+                }
+            }
+        })
+    }
+
+    ///
+    /// Preview the tree by keeping only the first `n` elements of every array, recursively, with
+    /// the string [`TRUNCATION_MARKER`] appended to the arrays that were cut short.
+    ///
+    pub fn sample(&self, n: usize) -> DataModel<'a> {
+        match self {
+            DataModel::Vec(vec) => {
+                let mut sample = vec
+                    .iter()
+                    .take(n)
+                    .map(|value| value.sample(n))
+                    .collect::<Vec<_>>();
+                if vec.len() > n {
+                    sample.push(DataModel::String(Cow::Borrowed(TRUNCATION_MARKER)));
+                }
+                DataModel::Vec(sample)
+            }
+            DataModel::Map(map) => DataModel::Map(
+                map.iter()
+                    .map(|(key, value)| (*key, value.sample(n)))
+                    .collect(),
+            ),
+            _ => self.clone(),
+        }
+    }
+
+    ///
+    /// Replace every masked value, verbatim or redacted, with the placeholder `with`.
+    ///
+    pub fn replace_masked(&mut self, with: &str) {
+        self.walk_mut(|node| {
+            if node.is_masked() {
+                *node = DataModel::String(Cow::Owned(with.to_string()));
+            }
+        })
+    }
+
+    ///
+    /// Keep only the elements of a vec, or the values of a map, for which `pred` returns `true`,
+    /// other variants are left untouched. Unlike [`DataModel::walk_mut`] this only looks at the
+    /// direct children.
+    ///
+    pub fn retain(&mut self, mut pred: impl FnMut(&DataModel<'a>) -> bool) {
+        match self {
+            DataModel::Vec(vec) => vec.retain(|value| pred(value)),
+            DataModel::Map(map) => map.retain(|_, value| pred(value)),
+            _ => {}
+        }
+    }
+
+    ///
+    /// Rename, recursively and in a single pass, every map key found in `table` to the key it
+    /// maps to. Renamed entries keep their position, and when a key is renamed onto one that is
+    /// already present the later entry wins.
+    ///
+    pub fn rename_keys_map(&mut self, table: &HashMap<&str, &'a str>) {
+        self.walk_mut(|node| {
+            if let DataModel::Map(map) = node {
+                if map.keys().any(|key| table.contains_key(key)) {
+                    *map = std::mem::take(map)
+                        .into_iter()
+                        .map(|(key, value)| (table.get(key).copied().unwrap_or(key), value))
+                        .collect();
+                }
+            }
+        })
+    }
+
+    /// Keep only the entries of a map for which `pred` returns `true`, see [`DataModel::retain`].
+    pub fn retain_entries(&mut self, mut pred: impl FnMut(&str, &DataModel<'a>) -> bool) {
+        if let DataModel::Map(map) = self {
+            map.retain(|key, value| pred(key, value));
+        }
+    }
+
+    ///
+    /// Build a new map holding only the values at the dotted `paths`, with the maps and vecs
+    /// leading to them recreated as needed. Paths go through map keys and array indices like
+    /// [`DataModel::coalesce`], elements skipped before a selected index are filled with
+    /// [`DataModel::Null`] so indices are kept. A path that does not resolve is ignored.
+    ///
+    pub fn select(&self, paths: &[&str]) -> DataModel<'a> {
+        let mut selected = DataModel::Map(IndexMap::new());
+
+        'paths: for path in paths.iter().filter(|path| !path.is_empty()) {
+            let Some(value) = resolve(self, path) else {
+                continue;
+            };
+
+            let mut source = self;
+            let mut target = &mut selected;
+            for segment in path.split('.') {
+                // a placeholder, or the root of a vec, takes the shape of the source
+                if std::mem::discriminant(source) != std::mem::discriminant(target) {
+                    *target = match source {
+                        DataModel::Vec(_) => DataModel::Vec(Vec::new()),
+                        _ => DataModel::Map(IndexMap::new()),
+                    };
+                }
+                target = match (source, target) {
+                    (DataModel::Map(from), DataModel::Map(into)) => {
+                        let Some((key, next)) = from.get_key_value(segment) else {
+                            continue 'paths;
+                        };
+                        source = next;
+                        into.entry(key).or_insert(DataModel::Null)
+                    }
+                    (DataModel::Vec(from), DataModel::Vec(into)) => {
+                        let Some(index) = segment
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|index| *index < from.len())
+                        else {
+                            continue 'paths;
+                        };
+                        source = &from[index];
+                        if into.len() <= index {
+                            into.resize(index + 1, DataModel::Null);
+                        }
+                        &mut into[index]
+                    }
+                    _ => continue 'paths,
+                };
+            }
+            *target = value.clone();
+        }
+
+        selected
+    }
+
+    ///
+    /// Remove every map entry whose value is null, recursively. Nulls inside vecs are kept since
+    /// their position is meaningful.
+    ///
+    pub fn strip_nulls(&mut self) {
+        self.walk_mut(|node| {
+            if let DataModel::Map(map) = node {
+                map.retain(|_, value| !matches!(value, DataModel::Null));
+            }
+        })
+    }
+
+    ///
+    /// Remove every map entry that is null or an empty map or vec, recursively and bottom-up so
+    /// that a map emptied by the removal is removed in turn. As for [`DataModel::strip_nulls`]
+    /// elements of vecs are kept, and the value itself is never removed.
+    ///
+    pub fn compact(&mut self) {
+        fn removable(value: &DataModel<'_>) -> bool {
+            match value {
+                DataModel::Null => true,
+                DataModel::Map(map) => map.is_empty(),
+                DataModel::Vec(vec) => vec.is_empty(),
+                _ => false,
+            }
+        }
+
+        match self {
+            DataModel::Map(map) => {
+                map.values_mut().for_each(DataModel::compact);
+                map.retain(|_, value| !removable(value));
+            }
+            DataModel::Vec(vec) => vec.iter_mut().for_each(DataModel::compact),
+            _ => {}
+        }
+    }
+
+    ///
+    /// Replace every tagged value, a single entry map keyed by a capitalized identifier such as
+    /// `{ "Some": value }` or `{ "PaymentIntentId": "pay_123" }`, with the value it wraps, the
+    /// way [`crate::Variants::Transparent`] would have parsed it. A tag of null, like the
+    /// `{ "Succeeded": null }` of [`crate::Units::Tagged`], becomes the string of its name
+    /// instead, so that the variant is not lost to [`DataModel::strip_nulls`]. `{ "Some": null }`
+    /// is kept as it is for the same reason. A tag cannot be told apart from a map that has a
+    /// single capitalized key, so this is only meant for tagged input.
+    ///
+    pub fn unwrap_tags(&mut self) {
+        fn is_tag(name: &str) -> bool {
+            name.starts_with(|item: char| item.is_ascii_uppercase())
+                && name
+                    .chars()
+                    .all(|item| item.is_ascii_alphanumeric() || item == '_')
+        }
+
+        self.walk_mut(|node| loop {
+            let value = match node {
+                DataModel::Map(map) if map.len() == 1 => match map.first() {
+                    Some((&"Some", DataModel::Null)) => None,
+                    Some((name, DataModel::Null)) if is_tag(name) => {
+                        Some(DataModel::String(Cow::Borrowed(*name)))
+                    }
+                    Some((name, _)) if is_tag(name) => map.pop().map(|(_, value)| value),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            match value {
+                Some(value) => *node = value,
+                None => break,
+            }
+        })
+    }
+
+    ///
+    /// Replace every float without a fractional part, like `50.0`, with the equal integer so
+    /// that whole numbers compare and serialize the same whichever way they were printed.
+    /// Floats outside of the [`i64`] range are kept.
+    ///
+    pub fn integral_floats(&mut self) {
+        self.walk_mut(|node| {
+            if let DataModel::Float(data) = node {
+                if data.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(data) {
+                    *node = DataModel::Integer(*data as i64);
+                }
+            }
+        })
+    }
+
+    ///
+    /// Order the entries of every map by key, recursively. Maps keep the order of the input
+    /// otherwise, sorting makes dumps of the same value printed in a different order line up.
+    ///
+    pub fn sort_keys(&mut self) {
+        self.walk_mut(|node| {
+            if let DataModel::Map(map) = node {
+                map.sort_keys();
+            }
+        })
+    }
+
+    ///
+    /// Turn every unit variant named in `names` into the string of its name, the way a quoted
+    /// `"Succeeded"` is parsed: the `{ "Succeeded": null }` of [`crate::Units::Tagged`] becomes
+    /// `"Succeeded"`. With the default [`crate::Units::String`] both forms already agree.
+    ///
+    pub fn unify_unit_variants(&mut self, names: &[&str]) {
+        self.walk_mut(|node| {
+            if let DataModel::Map(map) = node {
+                if let Some((name, DataModel::Null)) = map.first() {
+                    if map.len() == 1 && names.contains(name) {
+                        *node = DataModel::String(Cow::Borrowed(*name));
+                    }
+                }
+            }
+        })
+    }
+
+    ///
+    /// Run the cleanup passes selected in `options`: unit variants are unified and tags are
+    /// unwrapped first, so that the nulls wrapped by `Some` in tagged input are stripped as
+    /// they are in untagged input. Unwrapping never turns a value into null.
+    ///
+    pub fn normalize(&mut self, options: NormalizeOptions) {
+        if !options.unit_variants.is_empty() {
+            let names = options
+                .unit_variants
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            self.unify_unit_variants(&names);
+        }
+
+        if options.unwrap_tags {
+            self.unwrap_tags();
+        }
+
+        if options.strip_nulls {
+            self.strip_nulls();
+        }
+
+        if options.integral_floats {
+            self.integral_floats();
+        }
+
+        if options.sort_keys {
+            self.sort_keys();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use nom::error::ErrorKind;
+
+    use crate::*;
+
+    #[test]
+    fn test_apply_patch() {
+        let data = r#"Payment { amount: 100, currency: "USD", address: Address { city: "Banglore", zip: "560001" }, metadata: Metadata { order: 1 } }"#;
+        let mut parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        let patch = r#"{ "amount": 200, "currency": None, "address": { "city": "Berlin", "zip": None, "country": { "code": "DE" } }, "metadata": [] }"#;
+        let patch = root::<(&str, ErrorKind)>(patch).unwrap().1;
+
+        parse.apply_patch(&patch);
+
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("amount", DataModel::Integer(200)),
+                    ("metadata", DataModel::Vec(vec![])),
+                    (
+                        "address",
+                        DataModel::Map(
+                            [
+                                ("city", DataModel::String("Berlin".into())),
+                                (
+                                    "country",
+                                    DataModel::Map(
+                                        [("code", DataModel::String("DE".into()))].into()
+                                    )
+                                ),
+                            ]
+                            .into()
+                        )
+                    ),
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_replaces_non_map() {
+        let mut model = DataModel::Vec(vec![DataModel::Null]);
+        model.apply_patch(&DataModel::Boolean(true));
+        assert_eq!(model, DataModel::Boolean(true));
+    }
+
+    #[test]
+    fn test_truncate_strings() {
+        let data = r#"BrowserInfo { user_agent: String("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36"), language: String("en-GB"), emoji: "ééééééééééééééééééééééé" }"#;
+        let mut parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        parse.truncate_strings(20, "...");
+
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    (
+                        "user_agent",
+                        DataModel::String("Mozilla/5.0 (Macinto...".into())
+                    ),
+                    ("language", DataModel::String("en-GB".into())),
+                    ("emoji", DataModel::String("éééééééééééééééééééé...".into())),
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_replace_masked() {
+        let data = r#"Encryptable { inner: *** alloc::string::String ***, encrypted: [*** Encrypted ***], name: "visible" }"#;
+        let options = ParseOptions::new().masks(Masks::Preserve);
+        let mut parse = root_with::<(&str, ErrorKind)>(data, &options).unwrap().1;
+
+        assert_eq!(parse.count_masked(), 2);
+        parse.replace_masked("<redacted>");
+
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("inner", DataModel::String("<redacted>".into())),
+                    (
+                        "encrypted",
+                        DataModel::Vec(vec![DataModel::String("<redacted>".into())])
+                    ),
+                    ("name", DataModel::String("visible".into())),
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let data = r#"PaymentsRequest { payment_id: Some(PaymentIntentId("pay_123")), merchant_id: None, amount: Some(Value(6500)), fee: 25.0, rate: 0.5, connector: Some([Stripe(None)]), customer: Customer { id: None } }"#;
+        let options = ParseOptions::new()
+            .variants(Variants::Tagged)
+            .keep_some(true);
+        let mut parse = root_with::<(&str, ErrorKind)>(data, &options).unwrap().1;
+
+        parse.normalize(NormalizeOptions::new().unwrap_tags(true));
+
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("payment_id", DataModel::String("pay_123".into())),
+                    ("amount", DataModel::Integer(6500)),
+                    ("fee", DataModel::Integer(25)),
+                    ("rate", DataModel::Float(0.5)),
+                    (
+                        "connector",
+                        DataModel::Vec(vec![DataModel::String("Stripe".into())])
+                    ),
+                    ("customer", DataModel::Map([].into())),
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            parse.map_or_empty().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec![
+                "amount",
+                "connector",
+                "customer",
+                "fee",
+                "payment_id",
+                "rate"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_selected_passes() {
+        let data = r#"Request { id: Some(5), name: None, fee: 25.0 }"#;
+        let options = ParseOptions::new().keep_some(true);
+        let mut parse = root_with::<(&str, ErrorKind)>(data, &options).unwrap().1;
+
+        parse.normalize(
+            NormalizeOptions::new()
+                .integral_floats(false)
+                .sort_keys(false),
+        );
+
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    (
+                        "id",
+                        DataModel::Map([("Some", DataModel::Integer(5))].into())
+                    ),
+                    ("fee", DataModel::Float(25.0))
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            parse.map_or_empty().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec!["id", "fee"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_unit_variants() {
+        let data = r#"Payments { bare: Succeeded, quoted: "Succeeded", other: Failed }"#;
+        let options = ParseOptions::new().units(Units::Tagged);
+        let parse = root_with::<(&str, ErrorKind)>(data, &options).unwrap().1;
+
+        let mut unified = parse.clone();
+        unified.normalize(NormalizeOptions::new().unit_variants(["Succeeded", "Failed"]));
+        assert_eq!(unified["bare"], unified["quoted"]);
+        assert_eq!(
+            unified,
+            DataModel::Map(
+                [
+                    ("bare", DataModel::String("Succeeded".into())),
+                    ("quoted", DataModel::String("Succeeded".into())),
+                    ("other", DataModel::String("Failed".into())),
+                ]
+                .into()
+            )
+        );
+
+        // unwrapping turns the unit tags into their names, strip_nulls has nothing to remove
+        let mut plain = parse;
+        plain.normalize(NormalizeOptions::new().unwrap_tags(true));
+        assert_eq!(plain["bare"], DataModel::String("Succeeded".into()));
+        assert_eq!(plain["quoted"], DataModel::String("Succeeded".into()));
+        assert_eq!(plain["other"], DataModel::String("Failed".into()));
+        assert_eq!(plain, unified);
+    }
+
+    #[test]
+    fn test_normalize_keeps_single_key_maps() {
+        let mut parse = root::<(&str, ErrorKind)>(r#"{"USD": 5}"#).unwrap().1;
+        parse.normalize(NormalizeOptions::default());
+        assert_eq!(
+            parse,
+            DataModel::Map([("USD", DataModel::Integer(5))].into())
+        );
+
+        let mut parse = root::<(&str, ErrorKind)>(r#"Foo { m: {"US": "x"} }"#)
+            .unwrap()
+            .1;
+        parse.normalize(NormalizeOptions::default());
+        assert_eq!(
+            parse["m"],
+            DataModel::Map([("US", DataModel::String("x".into()))].into())
+        );
+    }
+
+    #[test]
+    fn test_unwrap_tags() {
+        let data = r#"Payment { status: Succeeded, wrapped: Some(None), id: PaymentId("pay_1") }"#;
+        let options = ParseOptions::new()
+            .variants(Variants::Tagged)
+            .units(Units::Tagged)
+            .keep_some(true);
+        let mut parse = root_with::<(&str, ErrorKind)>(data, &options).unwrap().1;
+
+        parse.unwrap_tags();
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("status", DataModel::String("Succeeded".into())),
+                    (
+                        "wrapped",
+                        DataModel::Map([("Some", DataModel::Null)].into())
+                    ),
+                    ("id", DataModel::String("pay_1".into())),
+                ]
+                .into()
+            )
+        );
+
+        parse.strip_nulls();
+        assert_eq!(parse.map_or_empty().count(), 3);
+
+        let mut parse = DataModel::Map([("US-1", DataModel::Null)].into());
+        parse.unwrap_tags();
+        assert_eq!(parse, DataModel::Map([("US-1", DataModel::Null)].into()));
+    }
+
+    #[test]
+    fn test_compact() {
+        let data = r#"Payment { id: "pay_1", shipping: Some(Shipping { address: Address { line2: None, tags: [] }, phone: None }), refunds: [None, {}], meta: {} }"#;
+        let mut parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        parse.compact();
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("id", DataModel::String("pay_1".into())),
+                    (
+                        "refunds",
+                        DataModel::Vec(vec![DataModel::Null, DataModel::Map(IndexMap::new())])
+                    ),
+                ]
+                .into()
+            )
+        );
+
+        let mut parse = root::<(&str, ErrorKind)>("Empty { a: None }").unwrap().1;
+        parse.compact();
+        assert_eq!(parse, DataModel::Map(IndexMap::new()));
+    }
+
+    #[test]
+    fn test_retain() {
+        let data = r#"Request { id: None, name: "x", tags: [1, None, 2], label: None }"#;
+        let mut parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        parse.retain(|value| !matches!(value, DataModel::Null));
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [
+                    ("name", DataModel::String("x".into())),
+                    (
+                        "tags",
+                        DataModel::Vec(vec![
+                            DataModel::Integer(1),
+                            DataModel::Null,
+                            DataModel::Integer(2)
+                        ])
+                    ),
+                ]
+                .into()
+            )
+        );
+
+        parse.retain_entries(|key, _| key != "name");
+        let DataModel::Map(map) = &mut parse else {
+            panic!("expected a map")
+        };
+        let tags = map.get_mut("tags").unwrap();
+        tags.retain(|value| value != &DataModel::Null);
+
+        assert_eq!(
+            parse,
+            DataModel::Map(
+                [(
+                    "tags",
+                    DataModel::Vec(vec![DataModel::Integer(1), DataModel::Integer(2)])
+                )]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_rename_keys_map() {
+        let data = r#"Payment { amount: 100, customer: Customer { cust_id: "cus_1", addr: Address { zip: "560001" } }, attempts: [Attempt { amount: 100 }] }"#;
+        let mut parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        let table = HashMap::from([
+            ("amount", "amount_minor"),
+            ("cust_id", "id"),
+            ("addr", "address"),
+        ]);
+        parse.rename_keys_map(&table);
+
+        let expected = r#"Payment { amount_minor: 100, customer: Customer { id: "cus_1", address: Address { zip: "560001" } }, attempts: [Attempt { amount_minor: 100 }] }"#;
+        assert_eq!(parse, root::<(&str, ErrorKind)>(expected).unwrap().1);
+        assert_eq!(
+            parse.keys_at("").unwrap(),
+            vec!["amount_minor", "customer", "attempts"]
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        let data = crate::tests::PAYMENT_REQUEST;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        assert_eq!(
+            parse.select(&[
+                "amount",
+                "shipping.address.city",
+                "shipping.missing",
+                "nope"
+            ]),
+            DataModel::Map(
+                [
+                    ("amount", DataModel::Integer(6500)),
+                    (
+                        "shipping",
+                        DataModel::Map(
+                            [(
+                                "address",
+                                DataModel::Map(
+                                    [("city", DataModel::String("Banglore".into()))].into()
+                                )
+                            )]
+                            .into()
+                        )
+                    ),
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            parse.select(&["shipping.address.city", "shipping"]),
+            parse.select(&["shipping"])
+        );
+        assert_eq!(
+            parse.select(&["shipping", "shipping.address.city"]),
+            parse.select(&["shipping"])
+        );
+    }
+
+    #[test]
+    fn test_select_indices() {
+        let data = r#"Order { items: [Item { id: 1, name: "a" }, Item { id: 2, name: "b" }, Item { id: 3, name: "c" }], total: 6 }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+        let item = |id| DataModel::Map([("id", DataModel::Integer(id))].into());
+
+        assert_eq!(
+            parse.select(&["items.0.id"]),
+            DataModel::Map([("items", DataModel::Vec(vec![item(1)]))].into())
+        );
+        assert_eq!(
+            parse.select(&["items.2.id", "items.0.id", "items.9.id", "total.0"]),
+            DataModel::Map(
+                [(
+                    "items",
+                    DataModel::Vec(vec![item(1), DataModel::Null, item(3)])
+                )]
+                .into()
+            )
+        );
+
+        let list = root::<(&str, ErrorKind)>("[Some(1), [2, 3]]").unwrap().1;
+        assert_eq!(
+            list.select(&["1.1"]),
+            DataModel::Vec(vec![
+                DataModel::Null,
+                DataModel::Vec(vec![DataModel::Null, DataModel::Integer(3)])
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_arrays_by_key() {
+        let a = r#"[Attempt { id: "a1", status: Pending, amount: 10 }, Attempt { id: "a2", status: Pending, amount: 20 }]"#;
+        let b =
+            r#"[Attempt { id: "a2", status: Charged }, Attempt { id: "a3", status: Failed }, 5]"#;
+        let a = root::<(&str, ErrorKind)>(a).unwrap().1;
+        let b = root::<(&str, ErrorKind)>(b).unwrap().1;
+
+        assert_eq!(
+            DataModel::merge_arrays_by_key(&a, &b, "id"),
+            DataModel::Vec(vec![
+                DataModel::Map(
+                    [
+                        ("id", DataModel::String("a1".into())),
+                        ("status", DataModel::String("Pending".into())),
+                        ("amount", DataModel::Integer(10)),
+                    ]
+                    .into()
+                ),
+                DataModel::Map(
+                    [
+                        ("id", DataModel::String("a2".into())),
+                        ("status", DataModel::String("Charged".into())),
+                        ("amount", DataModel::Integer(20)),
+                    ]
+                    .into()
+                ),
+                DataModel::Map(
+                    [
+                        ("id", DataModel::String("a3".into())),
+                        ("status", DataModel::String("Failed".into())),
+                    ]
+                    .into()
+                ),
+                DataModel::Integer(5),
+            ])
+        );
+        assert_eq!(
+            DataModel::merge_arrays_by_key(&a, &DataModel::Null, "id"),
+            DataModel::Null
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        let attempts = (0..100)
+            .map(|index| format!("Attempt {{ id: {}, refunds: [1, 2, 3, 4, 5, 6] }}", index))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let data = format!("Payment {{ attempts: [{}], tags: [1, 2] }}", attempts);
+        let parse = root::<(&str, ErrorKind)>(&data).unwrap().1;
+
+        let sample = parse.sample(5);
+        let DataModel::Map(map) = &sample else {
+            panic!("expected a map")
+        };
+        let attempts = map["attempts"].vec_or_empty();
+        assert_eq!(attempts.len(), 6);
+        assert_eq!(attempts[5], DataModel::String(TRUNCATION_MARKER.into()));
+        assert_eq!(
+            attempts[0],
+            DataModel::Map(
+                [
+                    ("id", DataModel::Integer(0)),
+                    (
+                        "refunds",
+                        DataModel::Vec(vec![
+                            DataModel::Integer(1),
+                            DataModel::Integer(2),
+                            DataModel::Integer(3),
+                            DataModel::Integer(4),
+                            DataModel::Integer(5),
+                            DataModel::String(TRUNCATION_MARKER.into()),
+                        ])
+                    )
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            map["tags"],
+            DataModel::Vec(vec![DataModel::Integer(1), DataModel::Integer(2)])
+        );
+    }
+}
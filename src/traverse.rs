@@ -0,0 +1,167 @@
+use crate::DataModel;
+
+impl<'a> DataModel<'a> {
+    ///
+    /// Visit every node of the tree in pre-order (the node itself, then its children) threading an
+    /// accumulator through `f`.
+    ///
+    pub fn fold<B>(&self, init: B, mut f: impl FnMut(B, &DataModel<'a>) -> B) -> B {
+        fn inner<'a, B>(
+            node: &DataModel<'a>,
+            acc: B,
+            f: &mut impl FnMut(B, &DataModel<'a>) -> B,
+        ) -> B {
+            let acc = f(acc, node);
+            match node {
+                DataModel::Map(map) => map.values().fold(acc, |acc, value| inner(value, acc, f)),
+                DataModel::Vec(vec) => vec.iter().fold(acc, |acc, value| inner(value, acc, f)),
+                _ => acc,
+            }
+        }
+
+        inner(self, init, &mut f)
+    }
+
+    ///
+    /// Visit every node of the tree mutably in pre-order, children are visited after `f` has run
+    /// on their parent so a replaced node is descended into.
+    ///
+    pub fn walk_mut(&mut self, mut f: impl FnMut(&mut DataModel<'a>)) {
+        fn inner<'a>(node: &mut DataModel<'a>, f: &mut impl FnMut(&mut DataModel<'a>)) {
+            f(node);
+            match node {
+                DataModel::Map(map) => map.values_mut().for_each(|value| inner(value, f)),
+                DataModel::Vec(vec) => vec.iter_mut().for_each(|value| inner(value, f)),
+                _ => {}
+            }
+        }
+
+        inner(self, &mut f)
+    }
+
+    ///
+    /// Iterate over every node of the tree in pre-order along with its dotted path, where array
+    /// elements use their index as the segment, e.g. `shipping.address.city` or `vector_int.2`.
+    /// The root itself has the empty path.
+    ///
+    pub(crate) fn nodes(&self) -> impl Iterator<Item = (String, &DataModel<'a>)> {
+        let mut stack = vec![(String::new(), self)];
+
+        std::iter::from_fn(move || {
+            let (path, node) = stack.pop()?;
+            match node {
+                DataModel::Map(map) => stack.extend(
+                    map.iter()
+                        .rev()
+                        .map(|(key, value)| (join_path(&path, key), value)),
+                ),
+                DataModel::Vec(vec) => stack.extend(
+                    vec.iter()
+                        .enumerate()
+                        .rev()
+                        .map(|(index, value)| (join_path(&path, &index.to_string()), value)),
+                ),
+                _ => {}
+            }
+            Some((path, node))
+        })
+    }
+
+    ///
+    /// Iterate over every scalar (non map, non vec) value of the tree along with its dotted
+    /// path, see [`DataModel::find`] for the path format.
+    ///
+    pub fn scalar_leaves(&self) -> impl Iterator<Item = (String, &DataModel<'a>)> {
+        self.nodes()
+            .filter(|(_, node)| !matches!(node, DataModel::Map(_) | DataModel::Vec(_)))
+    }
+
+    ///
+    /// Find the first node matching `pred` in a pre-order walk, returning it along with its
+    /// dotted path. Map keys and array indices are joined with `.`, e.g. `attempts.0.status`, and
+    /// the root itself has the empty path.
+    ///
+    pub fn find(&self, pred: impl Fn(&DataModel<'a>) -> bool) -> Option<(String, &DataModel<'a>)> {
+        self.nodes().find(|(_, node)| pred(node))
+    }
+}
+
+///
+/// Resolve a dotted path as produced by [`DataModel::find`], map keys and array indices joined
+/// with `.`, the empty path being the root itself.
+///
+pub(crate) fn resolve<'b, 'a>(node: &'b DataModel<'a>, path: &str) -> Option<&'b DataModel<'a>> {
+    if path.is_empty() {
+        return Some(node);
+    }
+
+    path.split('.').try_fold(node, |node, segment| match node {
+        DataModel::Map(map) => map.get(segment),
+        DataModel::Vec(vec) => vec.get(segment.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+pub(crate) fn join_path(prefix: &str, segment: &str) -> String {
+    match prefix.is_empty() {
+        true => segment.to_string(),
+        false => format!("{}.{}", prefix, segment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use nom::error::ErrorKind;
+
+    use crate::*;
+
+    #[test]
+    fn test_scalar_leaves() {
+        let data = r#"A { data: "123", value: Ba { item: 123, list: [1, "two"], empty: [] } }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        let leaves = parse.scalar_leaves().collect::<Vec<_>>();
+
+        assert_eq!(
+            leaves,
+            vec![
+                ("data".to_string(), &DataModel::String("123".into())),
+                ("value.item".to_string(), &DataModel::Integer(123)),
+                ("value.list.0".to_string(), &DataModel::Integer(1)),
+                ("value.list.1".to_string(), &DataModel::String("two".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scalar_leaves_root() {
+        let parse = DataModel::Boolean(true);
+        assert_eq!(
+            parse.scalar_leaves().collect::<Vec<_>>(),
+            vec![(String::new(), &DataModel::Boolean(true))]
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let data = r#"Payment { amounts: [100, 6500, 6500], nested: Inner { amount: 6500 } }"#;
+        let parse = root::<(&str, ErrorKind)>(data).unwrap().1;
+
+        let found = parse.find(|node| node == &DataModel::Integer(6500));
+        assert!(matches!(
+            found,
+            Some((ref path, &DataModel::Integer(_))) if path == "amounts.1"
+        ));
+
+        let found = parse.find(|node| matches!(node, DataModel::Vec(_)));
+        assert_eq!(found.map(|(path, _)| path), Some("amounts".to_string()));
+
+        assert_eq!(parse.find(|node| node.is_masked()), None);
+        assert_eq!(
+            parse.find(|node| matches!(node, DataModel::Map(_))),
+            Some((String::new(), &parse))
+        );
+    }
+}